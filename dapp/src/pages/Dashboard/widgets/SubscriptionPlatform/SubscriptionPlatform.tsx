@@ -50,7 +50,29 @@ const STATUS_LABELS: Record<number, string> = {
   3: 'Pending provider cancel',
   4: 'Cancelled by user',
   5: 'Cancelled by provider',
-  6: 'Cancelled (insufficient funds)'
+  6: 'Cancelled (insufficient funds)',
+  7: 'Completed',
+  8: 'Disputed'
+};
+
+// Variant names of the contract's `SubscriptionStatus`, in discriminant order.
+const STATUS_NAMES = [
+  'None',
+  'Active',
+  'PendingUserCancel',
+  'PendingProviderCancel',
+  'CancelledByUser',
+  'CancelledByProvider',
+  'CancelledInsufficientFunds',
+  'Completed',
+  'Disputed'
+];
+
+const toStatus = (value: any) => {
+  if (typeof value?.name === 'string') {
+    return Math.max(STATUS_NAMES.indexOf(value.name), 0);
+  }
+  return Number(value ?? 0);
 };
 
 const toWei = (value: string) => {
@@ -239,7 +261,7 @@ export const SubscriptionPlatform = () => {
           remainingBalance: value?.remaining_balance?.toString?.() ?? '0',
           lastPaymentBlock: Number(value?.last_payment_block ?? 0),
           nextPaymentBlock: Number(value?.next_payment_block ?? 0),
-          status: Number(value?.status ?? 0),
+          status: toStatus(value?.status),
           cancelEffectiveBlock: Number(value?.cancel_effective_block ?? 0)
         });
       } catch (err) {
@@ -286,7 +308,7 @@ export const SubscriptionPlatform = () => {
           remainingBalance: value?.remaining_balance?.toString?.() ?? '0',
           lastPaymentBlock: Number(value?.last_payment_block ?? 0),
           nextPaymentBlock: Number(value?.next_payment_block ?? 0),
-          status: Number(value?.status ?? 0),
+          status: toStatus(value?.status),
           cancelEffectiveBlock: Number(value?.cancel_effective_block ?? 0)
         });
       } catch (err) {
@@ -24,7 +24,7 @@ pub async fn scheduled_payments_contract_cli() {
     println!("Select action:");
     println!("1. Deploy Contract");
     println!("2. Issue Test Token (USDC)");
-    println!("3. Create Subscription (Deposit 100, Cost 10/min)");
+    println!("3. Subscribe to Service (Deposit 100)");
     println!("4. Trigger Payment");
     println!("5. Cancel Subscription");
     println!("6. View OUTGOING Subscriptions (As Client)");
@@ -36,7 +36,7 @@ pub async fn scheduled_payments_contract_cli() {
     match input.trim() {
         "1" => interact.deploy().await,
         "2" => interact.issue_token().await,
-        "3" => interact.create_subscription().await,
+        "3" => interact.subscribe().await,
         "4" => interact.trigger_payment().await,
         "5" => interact.cancel_subscription().await,
         "6" => interact.view_my_subscriptions(wallet_address).await,
@@ -101,7 +101,7 @@ impl ContractInteractor {
             .from(&self.wallet_address)
             .gas(60_000_000)
             .typed(SubscriptionContractProxy)
-            .init()
+            .init(OptionalValue::<u64>::None)
             .code(&self.contract_code)
             .returns(ReturnsNewAddress)
             .run()
@@ -152,33 +152,19 @@ impl ContractInteractor {
         self.state.save();
     }
 
-    async fn create_subscription(&mut self) {
+    async fn subscribe(&mut self) {
         let contract_address_str = self.state.contract_address.as_ref().expect("Deploy first!");
         let contract_address = Bech32Address::from_bech32_string(contract_address_str.clone());
         
         let token_id_str = self.state.token_id.as_ref().expect("Issue token first!");
         let token_id = TokenIdentifier::from(token_id_str.as_str()); 
         
-        // 1. Vendor Logic
-        println!("Enter vendor address (bech32), or press Enter to use your wallet:");
-        let mut vendor_input = String::new();
-        io::stdin().read_line(&mut vendor_input).unwrap();
-        
-        let vendor_address = if vendor_input.trim().is_empty() {
-            self.wallet_address.clone()
-        } else {
-            Bech32Address::from_bech32_string(vendor_input.trim().to_string())
-                .to_address()
-        };
-
-        // 2. Frequency Logic
-        println!("Enter frequency in seconds (e.g., 60 for 1 min, 86400 for 1 day):");
-        let mut freq_str = String::new();
-        io::stdin().read_line(&mut freq_str).unwrap();
-        let frequency: u64 = freq_str.trim().parse().unwrap_or(60);
+        println!("Enter Service ID to subscribe to:");
+        let mut id_str = String::new();
+        io::stdin().read_line(&mut id_str).unwrap();
+        let service_id: u64 = id_str.trim().parse().expect("Invalid ID");
 
         let deposit_amount = BigUint::from(100u64) * BigUint::from(10u64).pow(18); 
-        let cost_per_cycle = BigUint::from(10u64) * BigUint::from(10u64).pow(18); 
 
         self.interactor
             .tx()
@@ -186,12 +172,12 @@ impl ContractInteractor {
             .to(&contract_address)
             .gas(120_000_000) 
             .typed(SubscriptionContractProxy)
-            .create_subscription(vendor_address, cost_per_cycle, frequency)
+            .subscribe(service_id, OptionalValue::<BigUint<StaticApi>>::None, OptionalValue::<u64>::None, OptionalValue::<u64>::None)
             .single_esdt(&token_id, 0, &deposit_amount)
             .run()
             .await;
 
-        println!("Subscription Created!");
+        println!("Subscribed!");
     }
 
     async fn trigger_payment(&mut self) {
@@ -231,7 +217,7 @@ impl ContractInteractor {
             .to(&contract_address)
             .gas(10_000_000)
             .typed(SubscriptionContractProxy)
-            .cancel_subscription(sub_id)
+            .cancel_subscription_by_user(sub_id)
             .run()
             .await;
 
@@ -247,7 +233,7 @@ impl ContractInteractor {
             .query()
             .to(&contract_address)
             .typed(SubscriptionContractProxy)
-            .get_client_subscriptions(address)
+            .get_user_subscriptions(address)
             .returns(ReturnsResultUnmanaged) // FIX: Added this line
             .run()
             .await;
@@ -264,7 +250,7 @@ impl ContractInteractor {
             .query()
             .to(&contract_address)
             .typed(SubscriptionContractProxy)
-            .get_vendor_subscriptions(address)
+            .get_provider_subscriptions(address)
             .returns(ReturnsResultUnmanaged) // FIX: Added this line
             .run()
             .await;
@@ -0,0 +1,3 @@
+fn main() {
+    multiversx_sc_meta_lib::cli_main::<scheduled_payments_contract_v1::AbiProvider>();
+}
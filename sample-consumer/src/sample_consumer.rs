@@ -0,0 +1,35 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+use scheduled_payments_contract::subscription_access;
+
+/// Example partner contract: only subscribers of one service can claim perks.
+#[multiversx_sc::contract]
+pub trait SampleConsumer: subscription_access::SubscriptionAccessModule {
+    #[init]
+    fn init(&self, subscription_contract: ManagedAddress, service_id: u64) {
+        self.subscription_contract().set(&subscription_contract);
+        self.service_id().set(service_id);
+    }
+
+    #[endpoint(claimPerk)]
+    fn claim_perk(&self) {
+        let caller = self.blockchain().get_caller();
+        self.require_subscribed(&caller, self.service_id().get());
+        self.perks_claimed(&caller).update(|claimed| *claimed += 1);
+    }
+
+    #[view(getAccessUntil)]
+    fn get_access_until(&self, address: ManagedAddress) -> u64 {
+        self.access_until(&address, self.service_id().get())
+    }
+
+    #[view(getServiceId)]
+    #[storage_mapper("serviceId")]
+    fn service_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(getPerksClaimed)]
+    #[storage_mapper("perksClaimed")]
+    fn perks_claimed(&self, address: &ManagedAddress) -> SingleValueMapper<u64>;
+}
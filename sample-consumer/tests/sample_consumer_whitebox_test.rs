@@ -0,0 +1,141 @@
+use multiversx_sc_scenario::imports::*;
+use sample_consumer::*;
+use scheduled_payments_contract::SubscriptionContract;
+
+const OWNER: TestAddress = TestAddress::new("owner");
+const PROVIDER: TestAddress = TestAddress::new("provider");
+const CLIENT: TestAddress = TestAddress::new("client");
+const PAYMENTS_ADDRESS: TestSCAddress = TestSCAddress::new("scheduled-payments");
+const CONSUMER_ADDRESS: TestSCAddress = TestSCAddress::new("sample-consumer");
+const PAYMENTS_CODE_PATH: MxscPath = MxscPath::new("../output/scheduled-payments-contract.mxsc.json");
+const CONSUMER_CODE_PATH: MxscPath = MxscPath::new("output/sample-consumer.mxsc.json");
+
+const PRICE: u64 = 100;
+const FREQUENCY: u64 = 10;
+
+fn world() -> ScenarioWorld {
+    let mut blockchain = ScenarioWorld::new();
+    blockchain.register_contract(PAYMENTS_CODE_PATH, scheduled_payments_contract::ContractBuilder);
+    blockchain.register_contract(CONSUMER_CODE_PATH, sample_consumer::ContractBuilder);
+    blockchain
+}
+
+/// Deploys both contracts and creates service 1, which gates the consumer.
+fn setup() -> ScenarioWorld {
+    let mut world = world();
+    world.account(OWNER).nonce(1);
+    world.account(PROVIDER).nonce(1).balance(0);
+    world.account(CLIENT).nonce(1).balance(1_000);
+    world.current_block().block_nonce(1);
+
+    world
+        .tx()
+        .from(OWNER)
+        .raw_deploy()
+        .code(PAYMENTS_CODE_PATH)
+        .new_address(PAYMENTS_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.init(OptionalValue::<u64>::None);
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .raw_deploy()
+        .code(CONSUMER_CODE_PATH)
+        .new_address(CONSUMER_ADDRESS)
+        .whitebox(sample_consumer::contract_obj, |sc| {
+            sc.init(PAYMENTS_ADDRESS.to_managed_address(), 1);
+        });
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(PAYMENTS_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.register_as_provider();
+            sc.create_service(
+                ManagedBuffer::from("service"),
+                ManagedBuffer::from("description"),
+                BigUint::from(PRICE),
+                FREQUENCY,
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+        });
+    world
+        .tx()
+        .from(CLIENT)
+        .to(PAYMENTS_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.register_as_user();
+        });
+
+    world
+}
+
+fn claim_perk(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(CLIENT)
+        .to(CONSUMER_ADDRESS)
+        .whitebox(sample_consumer::contract_obj, |sc| {
+            sc.claim_perk();
+        });
+}
+
+#[test]
+fn perk_requires_subscription_test() {
+    let mut world = setup();
+    world
+        .tx()
+        .from(CLIENT)
+        .to(CONSUMER_ADDRESS)
+        .returns(ExpectError(4, "No active subscription"))
+        .whitebox(sample_consumer::contract_obj, |sc| {
+            sc.claim_perk();
+        });
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(PAYMENTS_ADDRESS)
+        .egld(150)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(1, OptionalValue::None, OptionalValue::None, OptionalValue::None);
+        });
+    claim_perk(&mut world);
+
+    world
+        .query()
+        .to(CONSUMER_ADDRESS)
+        .whitebox(sample_consumer::contract_obj, |sc| {
+            assert_eq!(sc.get_access_until(CLIENT.to_managed_address()), 1 + FREQUENCY);
+            assert_eq!(sc.perks_claimed(&CLIENT.to_managed_address()).get(), 1);
+        });
+}
+
+#[test]
+fn access_ends_with_paid_period_test() {
+    let mut world = setup();
+    world
+        .tx()
+        .from(CLIENT)
+        .to(PAYMENTS_ADDRESS)
+        .egld(150)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(1, OptionalValue::None, OptionalValue::None, OptionalValue::None);
+        });
+
+    world.current_block().block_nonce(FREQUENCY);
+    claim_perk(&mut world);
+
+    world.current_block().block_nonce(1 + FREQUENCY);
+    world
+        .tx()
+        .from(CLIENT)
+        .to(CONSUMER_ADDRESS)
+        .returns(ExpectError(4, "No active subscription"))
+        .whitebox(sample_consumer::contract_obj, |sc| {
+            sc.claim_perk();
+        });
+}
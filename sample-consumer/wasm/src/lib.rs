@@ -0,0 +1,29 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Endpoints:                            5
+// Async Callback (empty):               1
+// Total number of exported functions:   7
+
+#![no_std]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    sample_consumer
+    (
+        init => init
+        claimPerk => claim_perk
+        getAccessUntil => get_access_until
+        getServiceId => service_id
+        getPerksClaimed => perks_claimed
+        getSubscriptionContract => subscription_contract
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback_empty! {}
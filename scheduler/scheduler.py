@@ -25,6 +25,10 @@ from config import (
 )
 
 STATUS_ACTIVE = 1
+STATUS_PENDING_CANCEL = (2, 3)
+# Cancelled and completed subscriptions, including archived ones.
+STATUS_TERMINAL = (4, 5, 6, 7)
+STATUS_DISPUTED = 8
 
 
 def decode_u64(encoded: str) -> int:
@@ -97,6 +101,21 @@ def query_subscription_state(
     return status, next_block, cancel_block
 
 
+def query_dispute_deadline(provider: ProxyNetworkProvider, sub_id: int) -> int:
+    query = ContractQuery(
+        contract=Address.from_bech32(CONTRACT_ADDRESS),
+        function="getDispute",
+        encoded_arguments=[arg_to_hex(sub_id)],
+    )
+    response = provider.query_contract(query)
+    data = response.return_data or []
+    if not data or not data[0]:
+        return 0
+    raw = base64.b64decode(data[0])
+    # Dispute: opened_block (u64), deadline_block (u64), escrow (BigUint).
+    return int.from_bytes(raw[8:16], byteorder="big")
+
+
 def build_trigger_payment_tx(sender: Address, nonce: int, sub_id: int) -> Transaction:
     payload = f"triggerPayment@{arg_to_hex(sub_id)}"
     return Transaction(
@@ -125,6 +144,19 @@ def build_finalize_cancellation_tx(
     )
 
 
+def build_expire_dispute_tx(sender: Address, nonce: int, sub_id: int) -> Transaction:
+    payload = f"expireDispute@{arg_to_hex(sub_id)}"
+    return Transaction(
+        nonce=nonce,
+        sender=sender.to_bech32(),
+        receiver=Address.from_bech32(CONTRACT_ADDRESS).to_bech32(),
+        gas_limit=GAS_LIMIT,
+        chain_id=CHAIN_ID,
+        value=0,
+        data=TransactionPayload.from_str(payload).data,
+    )
+
+
 def get_current_block(provider: ProxyNetworkProvider) -> int:
     status = provider.get_network_status()
     return int(status.current_round)
@@ -155,9 +187,15 @@ def main() -> None:
 
             tx_computer = TransactionComputer()
             for sub_id in range(1, last_id + 1):
-                status, next_block, cancel_block = query_subscription_state(
-                    provider, sub_id
-                )
+                try:
+                    status, next_block, cancel_block = query_subscription_state(
+                        provider, sub_id
+                    )
+                except Exception as err:
+                    print(f"Skipping subscription {sub_id}: {err}")
+                    continue
+                if status in STATUS_TERMINAL:
+                    continue
                 if status == STATUS_ACTIVE:
                     if current_block < next_block:
                         continue
@@ -166,7 +204,22 @@ def main() -> None:
                     tx.signature = signer.sign(data_to_sign)
                     provider.send_transaction(tx)
                     nonce += 1
-                elif status in (2, 3) and current_block >= cancel_block:
+                elif status == STATUS_DISPUTED:
+                    # Cycles keep being charged into the dispute escrow; once
+                    # the arbiter's deadline passes, the dispute is settled
+                    # with the default share.
+                    deadline = query_dispute_deadline(provider, sub_id)
+                    if deadline and current_block >= deadline:
+                        tx = build_expire_dispute_tx(sender_address, nonce, sub_id)
+                    elif current_block >= next_block:
+                        tx = build_trigger_payment_tx(sender_address, nonce, sub_id)
+                    else:
+                        continue
+                    data_to_sign = tx_computer.compute_bytes_for_signing(tx)
+                    tx.signature = signer.sign(data_to_sign)
+                    provider.send_transaction(tx)
+                    nonce += 1
+                elif status in STATUS_PENDING_CANCEL and current_block >= cancel_block:
                     tx = build_finalize_cancellation_tx(
                         sender_address, nonce, sub_id
                     )
@@ -3,16 +3,165 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+pub mod scheduled_payments_proxy;
+pub mod subscription_access;
+
 const ROLE_NONE: u8 = 0;
 const ROLE_USER: u8 = 1;
 const ROLE_PROVIDER: u8 = 2;
 
-const STATUS_ACTIVE: u8 = 1;
-const STATUS_PENDING_USER_CANCEL: u8 = 2;
-const STATUS_PENDING_PROVIDER_CANCEL: u8 = 3;
-const STATUS_CANCELLED_BY_USER: u8 = 4;
-const STATUS_CANCELLED_BY_PROVIDER: u8 = 5;
-const STATUS_CANCELLED_INSUFFICIENT_FUNDS: u8 = 6;
+/// Version 1 is the original record layout, stored without a version.
+const LEGACY_SCHEMA_VERSION: u32 = 1;
+const SCHEMA_VERSION: u32 = 2;
+
+const DEFAULT_USAGE_DISPUTE_WINDOW_BLOCKS: u64 = 14_400;
+const DEFAULT_DISPUTE_TIMEOUT_BLOCKS: u64 = 100_800;
+const DEFAULT_DISPUTE_PROVIDER_SHARE_BPS: u64 = 5_000;
+const DEFAULT_PRICE_NOTICE_BLOCKS: u64 = 100_800;
+const DEFAULT_GOVERNANCE_DELAY_BLOCKS: u64 = 14_400;
+const DEFAULT_BOND_COOLDOWN_BLOCKS: u64 = 100_800;
+const BPS_DENOMINATOR: u64 = 10_000;
+
+const MAX_PAGE_SIZE: usize = 100;
+const MAX_SERVICE_TAGS: usize = 10;
+const PAYMENT_HISTORY_SIZE: u64 = 32;
+const DEACTIVATION_BATCH_SIZE: usize = 50;
+
+const HOOK_GAS_LIMIT: u64 = 10_000_000;
+const HOOK_CALLBACK_GAS: u64 = 2_000_000;
+const HOOK_GAS_RESERVE: u64 = 1_000_000;
+const HOOK_SUBSCRIPTION_STARTED: &str = "onSubscriptionStarted";
+const HOOK_SUBSCRIPTION_RENEWED: &str = "onSubscriptionRenewed";
+const HOOK_SUBSCRIPTION_ENDED: &str = "onSubscriptionEnded";
+const HOOK_SUBSCRIPTION_TRANSFERRED: &str = "onSubscriptionTransferred";
+
+/// Variants keep the discriminants of the former `u8` status constants,
+/// so stored subscriptions decode unchanged. `None` is only used before a
+/// subscription is created and, in list filters, to match any status.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubscriptionStatus {
+    None,
+    Active,
+    PendingUserCancel,
+    PendingProviderCancel,
+    CancelledByUser,
+    CancelledByProvider,
+    CancelledInsufficientFunds,
+    Completed,
+    Disputed,
+}
+
+impl SubscriptionStatus {
+    pub fn is_cancelled(&self) -> bool {
+        matches!(
+            self,
+            SubscriptionStatus::CancelledByUser
+                | SubscriptionStatus::CancelledByProvider
+                | SubscriptionStatus::CancelledInsufficientFunds
+        )
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.is_cancelled() || *self == SubscriptionStatus::Completed
+    }
+
+    pub fn is_pending_cancel(&self) -> bool {
+        matches!(
+            self,
+            SubscriptionStatus::PendingUserCancel | SubscriptionStatus::PendingProviderCancel
+        )
+    }
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccessMode {
+    Public,
+    Allowlist,
+    Blocklist,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeactivationMode {
+    StopNewSignups,
+    StopRenewals,
+    TerminateNow,
+}
+
+/// Progress of a deactivation that walks the service's subscriptions.
+/// `next_index` is the next position in `serviceSubscriptions` to process.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct ServiceDeactivation {
+    pub mode: DeactivationMode,
+    pub next_index: usize,
+    pub completed: bool,
+}
+
+/// An owner-controlled setting change. It is only applied once the
+/// governance delay has passed since it was proposed.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub enum GovernanceAction<M: ManagedTypeApi> {
+    SetUsageDisputeWindow(u64),
+    SetArbiter(ManagedAddress<M>),
+    SetServiceArbiter(u64, Option<ManagedAddress<M>>),
+    SetDisputeTimeout(u64),
+    SetDisputeDefaultProviderShare(u64),
+    SetMinPriceNotice(u64),
+    SetGovernanceDelay(u64),
+    SetServiceBond(BigUint<M>),
+    SetModerator(ManagedAddress<M>, bool),
+    SetBondCooldown(u64),
+    /// Sends the owner whatever the contract holds in the token beyond its
+    /// liabilities, such as stray direct transfers.
+    SweepSurplus(EgldOrEsdtTokenIdentifier<M>, u64),
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct GovernanceProposal<M: ManagedTypeApi> {
+    pub action: GovernanceAction<M>,
+    pub proposed_block: u64,
+    pub executable_block: u64,
+}
+
+/// Who is driving a status change. `Scheduler` covers the permissionless
+/// endpoints that anyone may call once a block is reached.
+#[derive(Clone, Copy, PartialEq)]
+enum Actor {
+    Subscriber,
+    Provider,
+    Arbiter,
+    Scheduler,
+}
+
+/// Every allowed subscription status change; anything else is rejected.
+fn is_allowed_transition(from: SubscriptionStatus, to: SubscriptionStatus, actor: Actor) -> bool {
+    use SubscriptionStatus::*;
+    match (from, to) {
+        (None, Active) => actor == Actor::Subscriber,
+        (Active, PendingUserCancel) => actor == Actor::Subscriber,
+        (Active, PendingProviderCancel) => actor == Actor::Provider,
+        (Active, Disputed) => actor == Actor::Subscriber,
+        (PendingUserCancel, Active) => actor == Actor::Subscriber,
+        (PendingProviderCancel, Active) => actor == Actor::Provider,
+        (Active, CancelledInsufficientFunds) => actor == Actor::Scheduler,
+        (Active, Completed) => actor == Actor::Scheduler,
+        (PendingUserCancel, CancelledByUser) => actor == Actor::Scheduler,
+        (PendingProviderCancel, CancelledByProvider) => {
+            actor == Actor::Scheduler || actor == Actor::Provider
+        },
+        (Active | PendingUserCancel | Disputed, CancelledByProvider) => actor == Actor::Provider,
+        (Disputed, Active) => actor == Actor::Arbiter || actor == Actor::Scheduler,
+        (CancelledByUser | CancelledByProvider | CancelledInsufficientFunds, Active) => {
+            actor == Actor::Subscriber
+        },
+        _ => false,
+    }
+}
 
 #[type_abi]
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
@@ -25,6 +174,17 @@ pub struct Service<M: ManagedTypeApi> {
     pub amount_per_cycle: BigUint<M>,
     pub frequency_in_blocks: u64,
     pub active: bool,
+    pub token_nonce: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct ServiceMetadata<M: ManagedTypeApi> {
+    pub category_id: u32,
+    pub tags: ManagedVec<M, ManagedBuffer<M>>,
+    pub details_uri: ManagedBuffer<M>,
+    pub content_hash: ManagedByteArray<M, 32>,
+    pub max_subscribers: u64,
 }
 
 #[type_abi]
@@ -40,16 +200,327 @@ pub struct Subscription<M: ManagedTypeApi> {
     pub remaining_balance: BigUint<M>,
     pub last_payment_block: u64,
     pub next_payment_block: u64,
-    pub status: u8,
+    pub status: SubscriptionStatus,
+    pub cancel_effective_block: u64,
+    pub max_total_spend: BigUint<M>,
+    pub max_cycles: u64,
+    pub end_block: u64,
+    pub total_spent: BigUint<M>,
+    pub cycles_paid: u64,
+    pub commitment_end_block: u64,
+    pub early_termination_fee: BigUint<M>,
+    pub escrowed_termination_fee: BigUint<M>,
+    pub price_epoch: u64,
+    pub seats: u64,
+    pub token_nonce: u64,
+}
+
+impl<M: ManagedTypeApi> Subscription<M> {
+    /// What one billing cycle costs: the per-seat price times the seats.
+    pub fn cycle_charge(&self) -> BigUint<M> {
+        &self.amount_per_cycle * self.seats
+    }
+}
+
+/// `Service` as stored by schema version 1.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct LegacyService<M: ManagedTypeApi> {
+    pub id: u64,
+    pub provider: ManagedAddress<M>,
+    pub name: ManagedBuffer<M>,
+    pub description: ManagedBuffer<M>,
+    pub token_identifier: EgldOrEsdtTokenIdentifier<M>,
+    pub amount_per_cycle: BigUint<M>,
+    pub frequency_in_blocks: u64,
+    pub active: bool,
+}
+
+/// `Subscription` as stored by schema version 1.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct LegacySubscription<M: ManagedTypeApi> {
+    pub id: u64,
+    pub service_id: u64,
+    pub client: ManagedAddress<M>,
+    pub vendor: ManagedAddress<M>,
+    pub token_identifier: EgldOrEsdtTokenIdentifier<M>,
+    pub amount_per_cycle: BigUint<M>,
+    pub frequency_in_blocks: u64,
+    pub remaining_balance: BigUint<M>,
+    pub last_payment_block: u64,
+    pub next_payment_block: u64,
+    pub status: SubscriptionStatus,
+    pub cancel_effective_block: u64,
+}
+
+/// Records with ids up to the `last_*` bounds, taken at upgrade time, are
+/// still in the legacy layout from the `next_*` ids onward.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct SchemaMigration {
+    pub from_version: u32,
+    pub next_service_id: u64,
+    pub last_service_id: u64,
+    pub next_subscription_id: u64,
+    pub last_subscription_id: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
+pub struct MeteringConfig<M: ManagedTypeApi> {
+    pub unit_price: BigUint<M>,
+    pub usage_cap_per_cycle: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
+pub struct UsageReport {
+    pub cycle_block: u64,
+    pub usage: u64,
+    pub reported_block: u64,
+    pub attested: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
+pub struct CommitmentTerms<M: ManagedTypeApi> {
+    pub min_cycles: u64,
+    pub early_termination_fee: BigUint<M>,
+}
+
+/// Scheduled change of a service's billing terms. Subscriptions move to
+/// it at their first cycle starting at or after `effective_block`.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct PriceChange<M: ManagedTypeApi> {
+    pub amount_per_cycle: BigUint<M>,
+    pub frequency_in_blocks: u64,
+    pub effective_block: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
+pub struct Dispute<M: ManagedTypeApi> {
+    pub opened_block: u64,
+    pub deadline_block: u64,
+    pub escrow: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct SubscriptionSummary<M: ManagedTypeApi> {
+    pub id: u64,
+    pub service_id: u64,
+    pub client: ManagedAddress<M>,
+    pub vendor: ManagedAddress<M>,
+    pub token_identifier: EgldOrEsdtTokenIdentifier<M>,
+    pub status: SubscriptionStatus,
+    pub total_spent: BigUint<M>,
+    pub cycles_paid: u64,
     pub cancel_effective_block: u64,
+    pub archived: bool,
+    pub token_nonce: u64,
+}
+
+/// `Shortfall` records an amount that was due but could not be collected.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettlementKind {
+    Initial,
+    Cycle,
+    Refund,
+    Fee,
+    Proration,
+    Shortfall,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Settlement<M: ManagedTypeApi> {
+    pub block: u64,
+    pub timestamp: u64,
+    pub amount: BigUint<M>,
+    pub kind: SettlementKind,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Invoice<M: ManagedTypeApi> {
+    pub subscription_id: u64,
+    pub service_id: u64,
+    pub amount: BigUint<M>,
+    pub token_identifier: EgldOrEsdtTokenIdentifier<M>,
+    pub block: u64,
+    pub token_nonce: u64,
+}
+
+/// `liabilities` is what the contract holds on behalf of others in one
+/// token: subscriber deposits and escrows, and service bonds.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Reconciliation<M: ManagedTypeApi> {
+    pub liabilities: BigUint<M>,
+    pub balance: BigUint<M>,
+    pub surplus: BigUint<M>,
+    pub deficit: BigUint<M>,
+}
+
+/// Subscriber churn counters, kept per service and per provider.
+/// Pending cancellations and disputes still count as active.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Default, PartialEq, Debug)]
+pub struct SubscriberStats {
+    pub active_subscribers: u64,
+    pub lifetime_subscriptions: u64,
+    pub cancelled_by_user: u64,
+    pub cancelled_by_provider: u64,
+    pub cancelled_insufficient_funds: u64,
+    pub completed: u64,
 }
 
 #[multiversx_sc::contract]
 pub trait SubscriptionContract {
+    /// The governance delay defaults to `DEFAULT_GOVERNANCE_DELAY_BLOCKS`.
     #[init]
-    fn init(&self) {
+    fn init(&self, opt_governance_delay: OptionalValue<u64>) {
         self.last_service_id().set(0);
         self.last_subscription_id().set(0);
+        self.usage_dispute_window().set(DEFAULT_USAGE_DISPUTE_WINDOW_BLOCKS);
+        self.dispute_timeout().set(DEFAULT_DISPUTE_TIMEOUT_BLOCKS);
+        self.dispute_default_provider_share().set(DEFAULT_DISPUTE_PROVIDER_SHARE_BPS);
+        self.min_price_notice().set(DEFAULT_PRICE_NOTICE_BLOCKS);
+        self.bond_cooldown().set(DEFAULT_BOND_COOLDOWN_BLOCKS);
+        self.governance_delay().set(
+            opt_governance_delay
+                .into_option()
+                .unwrap_or(DEFAULT_GOVERNANCE_DELAY_BLOCKS),
+        );
+        self.schema_version().set(SCHEMA_VERSION);
+    }
+
+    /// Settings introduced since the deployed version get their defaults.
+    /// Records in an older layout stay unreadable until `migrateRecords`
+    /// has been called enough times to complete the migration; until then,
+    /// subscribing, top-ups and scheduled payments fail with
+    /// "Migration in progress".
+    #[upgrade]
+    fn upgrade(&self) {
+        if self.schema_version().is_empty() {
+            self.schema_version().set(LEGACY_SCHEMA_VERSION);
+        }
+        let from_version = self.schema_version().get();
+        if from_version < SCHEMA_VERSION && self.schema_migration().is_empty() {
+            self.schema_migration().set(&SchemaMigration {
+                from_version,
+                next_service_id: 1,
+                last_service_id: self.last_service_id().get(),
+                next_subscription_id: 1,
+                last_subscription_id: self.last_subscription_id().get(),
+            });
+        }
+
+        if self.usage_dispute_window().is_empty() {
+            self.usage_dispute_window().set(DEFAULT_USAGE_DISPUTE_WINDOW_BLOCKS);
+        }
+        if self.dispute_timeout().is_empty() {
+            self.dispute_timeout().set(DEFAULT_DISPUTE_TIMEOUT_BLOCKS);
+        }
+        if self.dispute_default_provider_share().is_empty() {
+            self.dispute_default_provider_share().set(DEFAULT_DISPUTE_PROVIDER_SHARE_BPS);
+        }
+        if self.min_price_notice().is_empty() {
+            self.min_price_notice().set(DEFAULT_PRICE_NOTICE_BLOCKS);
+        }
+        if self.bond_cooldown().is_empty() {
+            self.bond_cooldown().set(DEFAULT_BOND_COOLDOWN_BLOCKS);
+        }
+        if self.governance_delay().is_empty() {
+            self.governance_delay().set(DEFAULT_GOVERNANCE_DELAY_BLOCKS);
+        }
+    }
+
+    // --- OWNER: MIGRATION ---
+
+    /// Re-encodes up to `batch_size` legacy records, services first, and
+    /// rebuilds the indexes and statistics derived from them. Returns true
+    /// once every record is in the current layout.
+    #[only_owner]
+    #[endpoint(migrateRecords)]
+    fn migrate_records(&self, batch_size: u64) -> bool {
+        require!(!self.schema_migration().is_empty(), "No migration in progress");
+        require!(batch_size > 0, "Batch size must be > 0");
+        let mut migration = self.schema_migration().get();
+
+        let mut remaining = batch_size;
+        while remaining > 0 && migration.next_service_id <= migration.last_service_id {
+            self.migrate_service(migration.next_service_id);
+            migration.next_service_id += 1;
+            remaining -= 1;
+        }
+        while remaining > 0 && migration.next_subscription_id <= migration.last_subscription_id {
+            self.migrate_subscription(migration.next_subscription_id);
+            migration.next_subscription_id += 1;
+            remaining -= 1;
+        }
+
+        let completed = migration.next_service_id > migration.last_service_id
+            && migration.next_subscription_id > migration.last_subscription_id;
+        if completed {
+            self.schema_migration().clear();
+            self.schema_version().set(SCHEMA_VERSION);
+        } else {
+            self.schema_migration().set(&migration);
+        }
+        completed
+    }
+
+    // --- OWNER: GOVERNANCE ---
+
+    /// Settings are never changed directly: the owner queues an action,
+    /// which can be executed once `governanceDelay` blocks have passed.
+    #[only_owner]
+    #[endpoint(proposeGovernanceAction)]
+    fn propose_governance_action(&self, action: GovernanceAction<Self::Api>) -> u64 {
+        self.validate_governance_action(&action);
+        let proposal_id = self.last_governance_proposal_id().get() + 1;
+        self.last_governance_proposal_id().set(proposal_id);
+
+        let current_block = self.blockchain().get_block_nonce();
+        let proposal = GovernanceProposal {
+            action,
+            proposed_block: current_block,
+            executable_block: current_block + self.governance_delay().get(),
+        };
+        self.governance_proposals(proposal_id).set(&proposal);
+        self.pending_governance_proposals().insert(proposal_id);
+        proposal_id
+    }
+
+    #[only_owner]
+    #[endpoint(executeGovernanceAction)]
+    fn execute_governance_action(&self, proposal_id: u64) {
+        require!(
+            self.pending_governance_proposals().contains(&proposal_id),
+            "Proposal not pending"
+        );
+        let proposal = self.governance_proposals(proposal_id).get();
+        require!(
+            self.blockchain().get_block_nonce() >= proposal.executable_block,
+            "Timelock not expired"
+        );
+        self.validate_governance_action(&proposal.action);
+        self.pending_governance_proposals().swap_remove(&proposal_id);
+        self.governance_proposals(proposal_id).clear();
+        self.apply_governance_action(proposal.action);
+    }
+
+    #[only_owner]
+    #[endpoint(cancelGovernanceAction)]
+    fn cancel_governance_action(&self, proposal_id: u64) {
+        require!(
+            self.pending_governance_proposals().swap_remove(&proposal_id),
+            "Proposal not pending"
+        );
+        self.governance_proposals(proposal_id).clear();
     }
 
     // --- REGISTRATION ---
@@ -76,7 +547,12 @@ pub trait SubscriptionContract {
 
     // --- SERVICE PROVIDER: SERVICES ---
 
+    /// A non-zero token nonce makes the service bill in that specific
+    /// SFT or Meta-ESDT (e.g. an LP token position). The EGLD service bond
+    /// must be paid in; it is returned when the service is retired.
+    #[payable("EGLD")]
     #[endpoint(createService)]
+    #[allow_multiple_var_args]
     fn create_service(
         &self,
         name: ManagedBuffer,
@@ -84,6 +560,7 @@ pub trait SubscriptionContract {
         amount_per_cycle: BigUint,
         frequency_in_blocks: u64,
         opt_token_id: OptionalValue<EgldOrEsdtTokenIdentifier>,
+        opt_token_nonce: OptionalValue<u64>,
     ) -> u64 {
         let caller = self.blockchain().get_caller();
         require!(
@@ -97,137 +574,637 @@ pub trait SubscriptionContract {
             OptionalValue::Some(token) => token,
             OptionalValue::None => EgldOrEsdtTokenIdentifier::egld(),
         };
+        let token_nonce = opt_token_nonce.into_option().unwrap_or_default();
+        require!(
+            token_nonce == 0 || token_identifier.is_esdt(),
+            "EGLD has no token nonce"
+        );
 
-        let service_id = self.last_service_id().get() + 1;
-        self.last_service_id().set(service_id);
-
-        let service = Service {
-            id: service_id,
-            provider: caller.clone(),
+        self.add_service(Service {
+            id: 0,
+            provider: caller,
             name,
             description,
             token_identifier,
+            token_nonce,
             amount_per_cycle,
             frequency_in_blocks,
             active: true,
-        };
+        })
+    }
 
-        self.services(service_id).set(&service);
-        self.service_ids().push(&service_id);
-        self.provider_services(&caller).push(&service_id);
+    /// A bundle is a service of its own, with its own price and billing,
+    /// whose subscriptions also give access to each of the member services.
+    /// Members must be active, non-bundle services of the caller that bill
+    /// in the same token. Bundles take a service bond as well.
+    #[payable("EGLD")]
+    #[endpoint(createBundle)]
+    fn create_bundle(
+        &self,
+        name: ManagedBuffer,
+        description: ManagedBuffer,
+        amount_per_cycle: BigUint,
+        frequency_in_blocks: u64,
+        service_ids: MultiValueEncoded<u64>,
+    ) -> u64 {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.user_role(&caller).get() == ROLE_PROVIDER,
+            "Only providers can create services"
+        );
+        require!(amount_per_cycle > 0, "Amount per cycle must be > 0");
+        require!(frequency_in_blocks > 0, "Frequency must be > 0");
+        require!(service_ids.len() >= 2, "Bundle needs at least two services");
+
+        let mut members = ManagedVec::<Self::Api, u64>::new();
+        for service_id in service_ids {
+            let service = self.services(service_id).get();
+            require!(service.provider == caller, "Only own services can be bundled");
+            require!(service.active, "Service is not active");
+            require!(
+                self.bundle_services(service_id).is_empty(),
+                "Bundles cannot be nested"
+            );
+            if !members.is_empty() {
+                let first = self.services(members.get(0)).get();
+                require!(
+                    service.token_identifier == first.token_identifier
+                        && service.token_nonce == first.token_nonce,
+                    "Bundled services must use the same token"
+                );
+            }
+            require!(!members.contains(&service_id), "Duplicate service");
+            members.push(service_id);
+        }
 
-        service_id
+        let first = self.services(members.get(0)).get();
+        let bundle_id = self.add_service(Service {
+            id: 0,
+            provider: caller,
+            name,
+            description,
+            token_identifier: first.token_identifier,
+            token_nonce: first.token_nonce,
+            amount_per_cycle,
+            frequency_in_blocks,
+            active: true,
+        });
+        for service_id in members.iter() {
+            self.bundle_services(bundle_id).insert(service_id);
+            self.service_bundles(service_id).insert(bundle_id);
+        }
+        bundle_id
     }
 
+    /// Stops new signups and, depending on the mode, winds down existing
+    /// subscriptions in batches; call `continueServiceDeactivation` until
+    /// the deactivation is completed. `TerminateNow` refunds the unused part
+    /// of the current cycle out of the payment still held for it.
     #[endpoint(deactivateService)]
-    fn deactivate_service(&self, service_id: u64) {
+    fn deactivate_service(&self, service_id: u64, opt_mode: OptionalValue<DeactivationMode>) {
         let caller = self.blockchain().get_caller();
         let mut service = self.services(service_id).get();
         require!(caller == service.provider, "Only provider can deactivate");
+        require!(service.active, "Service is not active");
         service.active = false;
         self.services(service_id).set(&service);
+        self.unindex_service(&service);
+        self.service_deactivated_block(service_id)
+            .set(self.blockchain().get_block_nonce());
+
+        let mode = opt_mode.into_option().unwrap_or(DeactivationMode::StopNewSignups);
+        self.service_deactivation(service_id).set(&ServiceDeactivation {
+            mode,
+            next_index: 1,
+            completed: mode == DeactivationMode::StopNewSignups,
+        });
+        self.process_deactivation_batch(&service);
     }
 
-    // --- USER: SUBSCRIPTIONS ---
+    #[endpoint(continueServiceDeactivation)]
+    fn continue_service_deactivation(&self, service_id: u64) {
+        self.require_service_provider(service_id);
+        require!(
+            !self.service_deactivation(service_id).is_empty()
+                && !self.service_deactivation(service_id).get().completed,
+            "No deactivation in progress"
+        );
+        let service = self.services(service_id).get();
+        self.process_deactivation_batch(&service);
+    }
 
-    #[payable("*")]
-    #[endpoint(subscribe)]
-    fn subscribe(&self, service_id: u64) -> u64 {
-        let caller = self.blockchain().get_caller();
+    #[endpoint(reactivateService)]
+    fn reactivate_service(&self, service_id: u64) {
+        self.require_service_provider(service_id);
+        let mut service = self.services(service_id).get();
+        require!(!service.active, "Service is already active");
+        require!(!self.service_delisted(service_id).get(), "Service is delisted");
         require!(
-            self.user_role(&caller).get() == ROLE_USER,
-            "Only users can subscribe"
+            self.service_deactivation(service_id).is_empty()
+                || self.service_deactivation(service_id).get().completed,
+            "Deactivation still in progress"
         );
+        service.active = true;
+        self.services(service_id).set(&service);
+        self.service_deactivation(service_id).clear();
+        self.index_service(&service);
+    }
 
+    /// Delists a deactivated service once it has no subscriptions left
+    /// running, and returns the service bond to the provider. The bond
+    /// stays slashable for `getBondCooldown` blocks after the service was
+    /// deactivated.
+    #[endpoint(retireService)]
+    fn retire_service(&self, service_id: u64) {
+        let provider = self.require_service_provider(service_id);
         let service = self.services(service_id).get();
-        require!(service.active, "Service is not active");
-
-        let (payment_token, payment_amount) =
-            self.call_value().egld_or_single_fungible_esdt();
+        require!(!service.active, "Service is still active");
         require!(
-            payment_token == service.token_identifier,
-            "Invalid payment token"
+            self.service_deactivation(service_id).is_empty()
+                || self.service_deactivation(service_id).get().completed,
+            "Deactivation still in progress"
         );
         require!(
-            payment_amount >= service.amount_per_cycle,
-            "Deposit must cover at least one cycle"
+            self.service_active_subscriptions(service_id).is_empty(),
+            "Service has active subscriptions"
+        );
+        require!(
+            self.blockchain().get_block_nonce()
+                >= self.service_deactivated_block(service_id).get()
+                    + self.bond_cooldown().get(),
+            "Retirement cooldown not over"
         );
+        self.remove_from_listing(service_id);
 
-        let current_block = self.blockchain().get_block_nonce();
+        let bond = self.service_bond(service_id).take();
+        if bond > 0 {
+            self.send().direct_egld(&provider, &bond);
+            self.remove_liability(&EgldOrEsdtTokenIdentifier::egld(), 0, &bond);
+        }
+    }
 
-        let mut remaining_balance = payment_amount;
-        self.send().direct(
-            &service.provider,
-            &service.token_identifier,
-            0,
-            &service.amount_per_cycle,
+    /// Schedules new billing terms for the service. A change that has not
+    /// taken effect yet is replaced; the notice period applies again.
+    #[endpoint(updateServicePrice)]
+    fn update_service_price(
+        &self,
+        service_id: u64,
+        amount_per_cycle: BigUint,
+        frequency_in_blocks: u64,
+        effective_block: u64,
+    ) {
+        self.require_service_provider(service_id);
+        require!(amount_per_cycle > 0, "Amount per cycle must be > 0");
+        require!(frequency_in_blocks > 0, "Frequency must be > 0");
+        require!(
+            effective_block >= self.blockchain().get_block_nonce() + self.min_price_notice().get(),
+            "Notice period too short"
         );
-        remaining_balance -= &service.amount_per_cycle;
 
-        let sub_id = self.last_subscription_id().get() + 1;
-        self.last_subscription_id().set(sub_id);
+        self.sync_service_price(service_id);
+        let applied_epoch = self.service_price_epoch(service_id).get();
+        let epoch = applied_epoch + 1;
+        self.price_change_count(service_id).set(epoch);
+        self.price_changes(service_id, epoch).set(&PriceChange {
+            amount_per_cycle,
+            frequency_in_blocks,
+            effective_block,
+        });
+    }
 
-        let subscription = Subscription {
-            id: sub_id,
-            service_id,
-            client: caller.clone(),
-            vendor: service.provider.clone(),
-            token_identifier: service.token_identifier.clone(),
-            amount_per_cycle: service.amount_per_cycle.clone(),
-            frequency_in_blocks: service.frequency_in_blocks,
-            remaining_balance,
-            last_payment_block: current_block,
-            next_payment_block: current_block + service.frequency_in_blocks,
-            status: STATUS_ACTIVE,
-            cancel_effective_block: 0,
-        };
+    /// `max_subscribers` of zero means no cap. Replaces any previous
+    /// metadata, including the category and tag listings.
+    #[endpoint(setServiceMetadata)]
+    fn set_service_metadata(
+        &self,
+        service_id: u64,
+        category_id: u32,
+        details_uri: ManagedBuffer,
+        content_hash: ManagedByteArray<32>,
+        max_subscribers: u64,
+        tags: MultiValueEncoded<ManagedBuffer>,
+    ) {
+        self.require_service_provider(service_id);
+        let service = self.services(service_id).get();
+        require!(tags.len() <= MAX_SERVICE_TAGS, "Too many tags");
+
+        self.unindex_service(&service);
+        self.service_metadata(service_id).set(&ServiceMetadata {
+            category_id,
+            tags: tags.to_vec(),
+            details_uri,
+            content_hash,
+            max_subscribers,
+        });
+        self.index_service(&service);
+    }
 
-        self.subscriptions(sub_id).set(&subscription);
-        self.cancel_requested_by_is_set(sub_id).set(false);
-        self.cancel_requested_by(sub_id).clear();
-        self.user_subscriptions(&caller).push(&sub_id);
-        self.provider_subscriptions(&service.provider).push(&sub_id);
-        self.service_subscriptions(service_id).push(&sub_id);
+    /// Metering applies to subscriptions created after this call;
+    /// a zero unit price turns metering off.
+    #[endpoint(setServiceMetering)]
+    fn set_service_metering(
+        &self,
+        service_id: u64,
+        unit_price: BigUint,
+        usage_cap_per_cycle: u64,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let service = self.services(service_id).get();
+        require!(caller == service.provider, "Only provider can set metering");
 
-        sub_id
+        if unit_price == 0 {
+            self.service_metering(service_id).clear();
+            return;
+        }
+        require!(usage_cap_per_cycle > 0, "Usage cap must be > 0");
+        self.service_metering(service_id).set(&MeteringConfig {
+            unit_price,
+            usage_cap_per_cycle,
+        });
     }
 
-    #[payable("*")]
-    #[endpoint(topUp)]
-    fn top_up(&self, sub_id: u64) {
+    /// Commitment terms apply to subscriptions created after this call;
+    /// zero minimum cycles removes the commitment.
+    #[endpoint(setServiceCommitment)]
+    fn set_service_commitment(
+        &self,
+        service_id: u64,
+        min_cycles: u64,
+        early_termination_fee: BigUint,
+    ) {
         let caller = self.blockchain().get_caller();
-        let mut sub = self.subscriptions(sub_id).get();
-        require!(caller == sub.client, "Only subscriber can top up");
-        let (payment_token, payment_amount) =
-            self.call_value().egld_or_single_fungible_esdt();
-        require!(
-            payment_token == sub.token_identifier,
-            "Invalid payment token"
-        );
-        require!(payment_amount > 0, "Top up amount must be > 0");
+        let service = self.services(service_id).get();
+        require!(caller == service.provider, "Only provider can set commitment");
 
-        sub.remaining_balance += payment_amount;
-        if sub.status != STATUS_ACTIVE {
-            sub.status = STATUS_ACTIVE;
-            let current_block = self.blockchain().get_block_nonce();
-            sub.last_payment_block = current_block;
-            sub.next_payment_block = current_block + sub.frequency_in_blocks;
+        if min_cycles == 0 {
+            self.service_commitment(service_id).clear();
+            return;
         }
-        self.subscriptions(sub_id).set(&sub);
+        self.service_commitment(service_id).set(&CommitmentTerms {
+            min_cycles,
+            early_termination_fee,
+        });
     }
 
-    #[endpoint(cancelSubscriptionByUser)]
-    fn cancel_subscription_by_user(&self, sub_id: u64) {
+    /// Registers a contract notified on subscription lifecycle changes:
+    /// `onSubscriptionStarted(sub_id, client, paid_until_block)`,
+    /// `onSubscriptionRenewed(sub_id, client, paid_until_block)`,
+    /// `onSubscriptionEnded(sub_id, client, status)` and
+    /// `onSubscriptionTransferred(sub_id, new_client, previous_client)`.
+    #[endpoint(setServiceHook)]
+    fn set_service_hook(&self, service_id: u64, opt_hook: OptionalValue<ManagedAddress>) {
+        self.require_service_provider(service_id);
+        match opt_hook {
+            OptionalValue::Some(hook) => {
+                require!(
+                    self.blockchain().is_smart_contract(&hook),
+                    "Hook must be a smart contract"
+                );
+                self.service_hook(service_id).set(&hook);
+            },
+            OptionalValue::None => self.service_hook(service_id).clear(),
+        }
+    }
+
+    /// Subscriptions are transferable unless the provider turns it off.
+    #[endpoint(setServiceTransferable)]
+    fn set_service_transferable(&self, service_id: u64, transferable: bool) {
+        self.require_service_provider(service_id);
+        self.service_non_transferable(service_id).set(!transferable);
+    }
+
+    // --- SERVICE PROVIDER: ACCESS CONTROL ---
+
+    #[endpoint(setServiceAccessMode)]
+    fn set_service_access_mode(&self, service_id: u64, mode: AccessMode) {
+        self.require_service_provider(service_id);
+        self.service_access_mode(service_id).set(mode);
+    }
+
+    #[endpoint(addToServiceAllowlist)]
+    fn add_to_service_allowlist(&self, service_id: u64, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_service_provider(service_id);
+        let mut allowlist = self.service_allowlist(service_id);
+        for address in addresses {
+            allowlist.insert(address);
+        }
+    }
+
+    #[endpoint(removeFromServiceAllowlist)]
+    fn remove_from_service_allowlist(
+        &self,
+        service_id: u64,
+        addresses: MultiValueEncoded<ManagedAddress>,
+    ) {
+        self.require_service_provider(service_id);
+        let mut allowlist = self.service_allowlist(service_id);
+        for address in addresses {
+            allowlist.swap_remove(&address);
+        }
+    }
+
+    /// With `cancel_existing`, active subscriptions the listed addresses
+    /// hold for this service are moved to provider-pending cancellation.
+    #[endpoint(addToServiceBlocklist)]
+    fn add_to_service_blocklist(
+        &self,
+        service_id: u64,
+        cancel_existing: bool,
+        addresses: MultiValueEncoded<ManagedAddress>,
+    ) {
+        let caller = self.require_service_provider(service_id);
+        let mut blocklist = self.service_blocklist(service_id);
+        for address in addresses {
+            if cancel_existing {
+                for sub_id in self.user_active_subscriptions(&address).iter() {
+                    let sub = self.subscriptions(sub_id).get();
+                    if sub.service_id == service_id && sub.status == SubscriptionStatus::Active {
+                        self.request_provider_cancel(sub, &caller);
+                    }
+                }
+            }
+            blocklist.insert(address);
+        }
+    }
+
+    #[endpoint(removeFromServiceBlocklist)]
+    fn remove_from_service_blocklist(
+        &self,
+        service_id: u64,
+        addresses: MultiValueEncoded<ManagedAddress>,
+    ) {
+        self.require_service_provider(service_id);
+        let mut blocklist = self.service_blocklist(service_id);
+        for address in addresses {
+            blocklist.swap_remove(&address);
+        }
+    }
+
+    // --- MODERATION ---
+
+    /// Stops new signups and removes the service from `serviceIds`.
+    /// Existing subscriptions keep running until they end.
+    #[endpoint(delistService)]
+    fn delist_service(&self, service_id: u64) {
+        self.require_moderator();
+        let mut service = self.services(service_id).get();
+        require!(!self.service_delisted(service_id).get(), "Service is delisted");
+        if service.active {
+            service.active = false;
+            self.services(service_id).set(&service);
+            self.unindex_service(&service);
+            self.service_deactivated_block(service_id)
+                .set(self.blockchain().get_block_nonce());
+            self.service_deactivation(service_id).set(&ServiceDeactivation {
+                mode: DeactivationMode::StopNewSignups,
+                next_index: 1,
+                completed: true,
+            });
+        }
+        self.remove_from_listing(service_id);
+    }
+
+    /// Sends the service bond to the contract owner.
+    #[endpoint(slashServiceBond)]
+    fn slash_service_bond(&self, service_id: u64) {
+        self.require_moderator();
+        require!(!self.service_bond(service_id).is_empty(), "No service bond");
+        let bond = self.service_bond(service_id).take();
+        self.send().direct_egld(&self.blockchain().get_owner_address(), &bond);
+        self.remove_liability(&EgldOrEsdtTokenIdentifier::egld(), 0, &bond);
+    }
+
+    // --- USER: SUBSCRIPTIONS ---
+
+    /// The optional limits default to zero, meaning unlimited.
+    #[payable("*")]
+    #[allow_multiple_var_args]
+    #[endpoint(subscribe)]
+    fn subscribe(
+        &self,
+        service_id: u64,
+        opt_max_total_spend: OptionalValue<BigUint>,
+        opt_max_cycles: OptionalValue<u64>,
+        opt_end_block: OptionalValue<u64>,
+    ) -> u64 {
+        self.require_not_migrating();
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.user_role(&caller).get() == ROLE_USER,
+            "Only users can subscribe"
+        );
+
+        let service = self.sync_service_price(service_id);
+        require!(service.active, "Service is not active");
+        require!(
+            self.has_service_access(service_id, &caller),
+            "Not allowed to subscribe to this service"
+        );
+        require!(
+            self.bundle_open_to(service_id, &caller),
+            "Not allowed to use a bundled service"
+        );
+        require!(!self.service_is_full(service_id), "Service is full");
+
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(
+            payment.token_identifier == service.token_identifier
+                && payment.token_nonce == service.token_nonce,
+            "Invalid payment token"
+        );
+        let payment_amount = payment.amount;
+        require!(
+            payment_amount >= service.amount_per_cycle,
+            "Deposit must cover at least one cycle"
+        );
+
+        let current_block = self.blockchain().get_block_nonce();
+        let max_total_spend = opt_max_total_spend
+            .into_option()
+            .unwrap_or_else(BigUint::zero);
+        let max_cycles = opt_max_cycles.into_option().unwrap_or_default();
+        let end_block = opt_end_block.into_option().unwrap_or_default();
+        require!(
+            max_total_spend == 0 || max_total_spend >= service.amount_per_cycle,
+            "Spending cap must cover at least one cycle"
+        );
+        require!(
+            end_block == 0 || end_block > current_block,
+            "End block must be in the future"
+        );
+
+        let (commitment_end_block, early_termination_fee) =
+            if self.service_commitment(service_id).is_empty() {
+                (0, BigUint::zero())
+            } else {
+                let terms = self.service_commitment(service_id).get();
+                (
+                    current_block + terms.min_cycles * service.frequency_in_blocks,
+                    terms.early_termination_fee,
+                )
+            };
+
+        let sub_id = self.last_subscription_id().get() + 1;
+        self.last_subscription_id().set(sub_id);
+
+        let mut subscription = Subscription {
+            id: sub_id,
+            service_id,
+            client: caller.clone(),
+            vendor: service.provider.clone(),
+            token_identifier: service.token_identifier.clone(),
+            token_nonce: service.token_nonce,
+            amount_per_cycle: service.amount_per_cycle.clone(),
+            frequency_in_blocks: service.frequency_in_blocks,
+            remaining_balance: &payment_amount - &service.amount_per_cycle,
+            last_payment_block: current_block,
+            next_payment_block: current_block + service.frequency_in_blocks,
+            status: SubscriptionStatus::None,
+            cancel_effective_block: 0,
+            max_total_spend,
+            max_cycles,
+            end_block,
+            total_spent: service.amount_per_cycle.clone(),
+            cycles_paid: 1,
+            commitment_end_block,
+            early_termination_fee,
+            escrowed_termination_fee: BigUint::zero(),
+            price_epoch: self.service_price_epoch(service_id).get(),
+            seats: 1,
+        };
+        self.transition(&mut subscription, SubscriptionStatus::Active, Actor::Subscriber);
+        self.record_deposit(&subscription, &payment_amount);
+        self.hold_cycle_payment(&subscription, &service.amount_per_cycle);
+        self.record_settlement(&subscription, &service.amount_per_cycle, SettlementKind::Initial);
+
+        self.subscriptions(sub_id).set(&subscription);
+        self.cancel_requested_by_is_set(sub_id).set(false);
+        self.cancel_requested_by(sub_id).clear();
+        self.user_subscriptions(&caller).push(&sub_id);
+        self.provider_subscriptions(&service.provider).push(&sub_id);
+        self.service_subscriptions(service_id).push(&sub_id);
+        self.user_active_subscriptions(&caller).insert(sub_id);
+        self.provider_active_subscriptions(&service.provider).insert(sub_id);
+        self.service_active_subscriptions(service_id).insert(sub_id);
+        if !self.service_metering(service_id).is_empty() {
+            let metering = self.service_metering(service_id).get();
+            self.subscription_metering(sub_id).set(&metering);
+        }
+        self.notify_hook(&subscription, HOOK_SUBSCRIPTION_STARTED, subscription.next_payment_block);
+
+        sub_id
+    }
+
+    #[payable("*")]
+    #[endpoint(topUp)]
+    fn top_up(&self, sub_id: u64) {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().egld_or_single_esdt();
+        self.apply_top_up(&caller, sub_id, &payment);
+    }
+
+    /// Tops up several subscriptions in one multi-transfer; the n-th
+    /// payment goes to the n-th subscription id.
+    #[payable("*")]
+    #[endpoint(topUpMany)]
+    fn top_up_many(&self, sub_ids: MultiValueEncoded<u64>) {
+        let caller = self.blockchain().get_caller();
+        let payments = self.call_value().all_transfers();
+        require!(
+            payments.len() == sub_ids.len(),
+            "Payments do not match subscriptions"
+        );
+        for (sub_id, payment) in sub_ids.into_iter().zip(payments.iter()) {
+            self.apply_top_up(&caller, sub_id, &payment);
+        }
+    }
+
+    /// Lets the provider allow (or stop allowing) a cancelled subscription
+    /// to be revived by its subscriber.
+    #[endpoint(setRevivalApproval)]
+    fn set_revival_approval(&self, sub_id: u64, approved: bool) {
+        let caller = self.blockchain().get_caller();
+        self.require_not_archived(sub_id);
+        let sub = self.subscriptions(sub_id).get();
+        require!(caller == sub.vendor, "Only provider can approve revival");
+        require!(sub.status.is_cancelled(), "Subscription not cancelled");
+        self.revival_approved(sub_id).set(approved);
+    }
+
+    /// Restarts a cancelled subscription the provider approved for revival,
+    /// charging a new first cycle from the deposit.
+    #[payable("*")]
+    #[endpoint(reviveSubscription)]
+    fn revive_subscription(&self, sub_id: u64) {
         let caller = self.blockchain().get_caller();
+        self.require_not_archived(sub_id);
         let mut sub = self.subscriptions(sub_id).get();
-        require!(caller == sub.client, "Only subscriber can cancel");
+        require!(caller == sub.client, "Only subscriber can revive");
+        require!(self.revival_approved(sub_id).get(), "Revival not approved");
         require!(
-            sub.status == STATUS_ACTIVE,
-            "Subscription not active"
+            self.services(sub.service_id).get().active,
+            "Service is not active"
         );
+        require!(
+            self.has_service_access(sub.service_id, &caller),
+            "Not allowed to subscribe to this service"
+        );
+        require!(!self.service_is_full(sub.service_id), "Service is full");
+        self.apply_price_changes(&mut sub, self.blockchain().get_block_nonce());
+
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(
+            payment.token_identifier == sub.token_identifier
+                && payment.token_nonce == sub.token_nonce,
+            "Invalid payment token"
+        );
+        let payment_amount = payment.amount;
+        let cycle_charge = sub.cycle_charge();
+        require!(
+            payment_amount >= cycle_charge,
+            "Deposit must cover at least one cycle"
+        );
+
+        let current_block = self.blockchain().get_block_nonce();
+        require!(
+            !self.spending_limit_reached(&sub, &cycle_charge, current_block),
+            "Spending limit reached"
+        );
+        self.transition(&mut sub, SubscriptionStatus::Active, Actor::Subscriber);
+
+        self.record_deposit(&sub, &payment_amount);
+        self.hold_cycle_payment(&sub, &cycle_charge);
+        self.record_settlement(&sub, &cycle_charge, SettlementKind::Initial);
+        sub.remaining_balance = payment_amount - &cycle_charge;
+        sub.total_spent += &cycle_charge;
+        sub.cycles_paid += 1;
+        sub.last_payment_block = current_block;
+        sub.next_payment_block = current_block + sub.frequency_in_blocks;
+        sub.cancel_effective_block = 0;
+        self.subscriptions(sub_id).set(&sub);
+        self.revival_approved(sub_id).clear();
+        self.user_active_subscriptions(&sub.client).insert(sub_id);
+        self.provider_active_subscriptions(&sub.vendor).insert(sub_id);
+        self.service_active_subscriptions(sub.service_id).insert(sub_id);
+        self.notify_hook(&sub, HOOK_SUBSCRIPTION_STARTED, sub.next_payment_block);
+    }
+
+    #[endpoint(cancelSubscriptionByUser)]
+    fn cancel_subscription_by_user(&self, sub_id: u64) {
+        let caller = self.blockchain().get_caller();
+        let mut sub = self.subscriptions(sub_id).get();
+        require!(caller == sub.client, "Only subscriber can cancel");
 
-        sub.status = STATUS_PENDING_USER_CANCEL;
+        self.transition(&mut sub, SubscriptionStatus::PendingUserCancel, Actor::Subscriber);
         sub.cancel_effective_block = sub.next_payment_block;
+        if sub.cancel_effective_block < sub.commitment_end_block {
+            let fee = core::cmp::min(
+                sub.early_termination_fee.clone(),
+                sub.remaining_balance.clone(),
+            );
+            sub.remaining_balance -= &fee;
+            sub.escrowed_termination_fee = fee;
+        }
         self.subscriptions(sub_id).set(&sub);
         self.cancel_requested_by(sub_id).set(&caller);
         self.cancel_requested_by_is_set(sub_id).set(true);
@@ -236,107 +1213,1452 @@ pub trait SubscriptionContract {
     #[endpoint(cancelSubscriptionByProvider)]
     fn cancel_subscription_by_provider(&self, sub_id: u64) {
         let caller = self.blockchain().get_caller();
-        let mut sub = self.subscriptions(sub_id).get();
+        let sub = self.subscriptions(sub_id).get();
         require!(caller == sub.vendor, "Only provider can cancel");
+
+        self.request_provider_cancel(sub, &caller);
+    }
+
+    /// Withdraws a pending cancellation before it takes effect. Only the
+    /// party that requested it can revoke it; the billing schedule is kept.
+    #[endpoint(revokeCancellation)]
+    fn revoke_cancellation(&self, sub_id: u64) {
+        let caller = self.blockchain().get_caller();
+        let mut sub = self.subscriptions(sub_id).get();
+        require!(sub.status.is_pending_cancel(), "Subscription not pending cancel");
         require!(
-            sub.status == STATUS_ACTIVE,
-            "Subscription not active"
+            self.cancel_requested_by_is_set(sub_id).get()
+                && caller == self.cancel_requested_by(sub_id).get(),
+            "Only the requesting party can revoke"
+        );
+        require!(
+            self.blockchain().get_block_nonce() < sub.cancel_effective_block,
+            "Cancellation already effective"
         );
 
-        sub.status = STATUS_PENDING_PROVIDER_CANCEL;
-        sub.cancel_effective_block = sub.next_payment_block;
+        let actor = if sub.status == SubscriptionStatus::PendingUserCancel {
+            Actor::Subscriber
+        } else {
+            Actor::Provider
+        };
+        self.transition(&mut sub, SubscriptionStatus::Active, actor);
+        sub.cancel_effective_block = 0;
+        if sub.escrowed_termination_fee > 0 {
+            sub.remaining_balance += &sub.escrowed_termination_fee;
+            sub.escrowed_termination_fee = BigUint::zero();
+        }
         self.subscriptions(sub_id).set(&sub);
-        self.cancel_requested_by(sub_id).set(&caller);
-        self.cancel_requested_by_is_set(sub_id).set(true);
+        self.cancel_requested_by(sub_id).clear();
+        self.cancel_requested_by_is_set(sub_id).set(false);
     }
 
-    // --- SCHEDULER: PAYMENTS ---
+    // --- USER: TRANSFERS ---
 
-    #[endpoint(triggerPayment)]
-    fn trigger_payment(&self, sub_id: u64) {
+    /// First step of handing an active subscription, with its remaining
+    /// balance, to another user. Replaces any earlier proposal.
+    #[endpoint(proposeSubscriptionTransfer)]
+    fn propose_subscription_transfer(&self, sub_id: u64, recipient: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        let sub = self.subscriptions(sub_id).get();
+        require!(caller == sub.client, "Only subscriber can transfer");
+        require!(
+            sub.status == SubscriptionStatus::Active,
+            "Only active subscriptions can be transferred"
+        );
+        require!(
+            !self.service_non_transferable(sub.service_id).get(),
+            "Service does not allow transfers"
+        );
+        require!(recipient != caller, "Cannot transfer to yourself");
+        self.pending_transfer(sub_id).set(&recipient);
+    }
+
+    #[endpoint(cancelSubscriptionTransfer)]
+    fn cancel_subscription_transfer(&self, sub_id: u64) {
+        let caller = self.blockchain().get_caller();
+        let sub = self.subscriptions(sub_id).get();
+        require!(caller == sub.client, "Only subscriber can cancel transfer");
+        require!(!self.pending_transfer(sub_id).is_empty(), "No pending transfer");
+        self.pending_transfer(sub_id).clear();
+    }
+
+    /// The subscription stays in the previous owner's history; only their
+    /// active set drops it. Team members added by the previous owner lose
+    /// their access.
+    #[endpoint(acceptSubscriptionTransfer)]
+    fn accept_subscription_transfer(&self, sub_id: u64) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.pending_transfer(sub_id).is_empty()
+                && caller == self.pending_transfer(sub_id).get(),
+            "No transfer proposed to caller"
+        );
+        require!(
+            self.user_role(&caller).get() == ROLE_USER,
+            "Only users can subscribe"
+        );
+        let mut sub = self.subscriptions(sub_id).get();
+        require!(
+            sub.status == SubscriptionStatus::Active,
+            "Only active subscriptions can be transferred"
+        );
+        require!(
+            !self.service_non_transferable(sub.service_id).get(),
+            "Service does not allow transfers"
+        );
+        require!(
+            self.has_service_access(sub.service_id, &caller),
+            "Not allowed to subscribe to this service"
+        );
+        require!(
+            self.bundle_open_to(sub.service_id, &caller),
+            "Not allowed to use a bundled service"
+        );
+
+        let previous_client = core::mem::replace(&mut sub.client, caller.clone());
+        self.subscriptions(sub_id).set(&sub);
+        self.pending_transfer(sub_id).clear();
+        self.clear_subscription_members(sub_id);
+
+        self.user_subscriptions(&caller).push(&sub_id);
+        self.user_active_subscriptions(&previous_client).swap_remove(&sub_id);
+        self.user_active_subscriptions(&caller).insert(sub_id);
+        self.notify_hook(&sub, HOOK_SUBSCRIPTION_TRANSFERRED, previous_client);
+    }
+
+    // --- USER: TEAM SEATS ---
+
+    /// Extra seats are charged right away from the balance, prorated to
+    /// the rest of the current cycle. Fewer seats take effect from the
+    /// next cycle, without a refund.
+    #[endpoint(setSeats)]
+    fn set_seats(&self, sub_id: u64, seats: u64) {
+        let caller = self.blockchain().get_caller();
         let mut sub = self.subscriptions(sub_id).get();
-        require!(sub.status == STATUS_ACTIVE, "Subscription not active");
+        require!(caller == sub.client, "Only subscriber can change seats");
+        require!(sub.status == SubscriptionStatus::Active, "Subscription not active");
+        require!(seats > 0, "Seats must be > 0");
+        require!(
+            self.subscription_members(sub_id).len() as u64 <= seats,
+            "More members than seats"
+        );
+
+        if seats > sub.seats {
+            let current_block = self.blockchain().get_block_nonce();
+            let unused_blocks = core::cmp::min(
+                sub.next_payment_block.saturating_sub(current_block),
+                sub.frequency_in_blocks,
+            );
+            let prorated = &sub.amount_per_cycle * (seats - sub.seats) * unused_blocks
+                / sub.frequency_in_blocks;
+            require!(
+                sub.max_total_spend == 0 || &sub.total_spent + &prorated <= sub.max_total_spend,
+                "Spending limit reached"
+            );
+            require!(
+                sub.remaining_balance >= prorated,
+                "Insufficient balance for extra seats"
+            );
+            if prorated > 0 {
+                self.held_payment(sub_id).update(|held| *held += &prorated);
+                self.record_settlement(&sub, &prorated, SettlementKind::Proration);
+                sub.remaining_balance -= &prorated;
+                sub.total_spent += &prorated;
+            }
+        }
+        sub.seats = seats;
+        self.subscriptions(sub_id).set(&sub);
+    }
+
+    #[endpoint(addSubscriptionMembers)]
+    fn add_subscription_members(&self, sub_id: u64, members: MultiValueEncoded<ManagedAddress>) {
+        let caller = self.blockchain().get_caller();
+        let sub = self.subscriptions(sub_id).get();
+        require!(caller == sub.client, "Only subscriber can manage members");
+        require!(!sub.status.is_terminal(), "Subscription is terminated");
+        let mut subscription_members = self.subscription_members(sub_id);
+        for member in members {
+            require!(
+                self.has_service_access(sub.service_id, &member),
+                "Not allowed to subscribe to this service"
+            );
+            subscription_members.insert(member.clone());
+            self.member_subscriptions(&member).insert(sub_id);
+        }
+        require!(
+            subscription_members.len() as u64 <= sub.seats,
+            "More members than seats"
+        );
+    }
+
+    #[endpoint(removeSubscriptionMembers)]
+    fn remove_subscription_members(&self, sub_id: u64, members: MultiValueEncoded<ManagedAddress>) {
+        let caller = self.blockchain().get_caller();
+        let sub = self.subscriptions(sub_id).get();
+        require!(caller == sub.client, "Only subscriber can manage members");
+        let mut subscription_members = self.subscription_members(sub_id);
+        for member in members {
+            subscription_members.swap_remove(&member);
+            self.member_subscriptions(&member).swap_remove(&sub_id);
+        }
+    }
+
+    // --- METERED USAGE ---
+
+    /// An attestation is the subscriber's ed25519 signature over
+    /// `sc_address | sub_id | cycle_block | usage`, the numbers encoded
+    /// as 8-byte big-endian, where `cycle_block` is the subscription's
+    /// `next_payment_block` at the time of the report.
+    /// Attested reports skip the dispute window.
+    #[endpoint(reportUsage)]
+    fn report_usage(
+        &self,
+        sub_id: u64,
+        usage: u64,
+        opt_attestation: OptionalValue<ManagedBuffer>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let sub = self.subscriptions(sub_id).get();
+        require!(caller == sub.vendor, "Only provider can report usage");
+        require!(
+            !self.subscription_metering(sub_id).is_empty(),
+            "Subscription is not metered"
+        );
+        let is_billable = sub.status == SubscriptionStatus::Active
+            || sub.status == SubscriptionStatus::Disputed
+            || sub.status.is_pending_cancel();
+        require!(is_billable, "Subscription not active");
+        require!(
+            self.pending_usage(sub_id).is_empty(),
+            "Previous usage report not settled"
+        );
+        require!(
+            self.last_usage_report_cycle(sub_id).get() < sub.next_payment_block,
+            "Usage already reported for this cycle"
+        );
+
+        let attested = match opt_attestation {
+            OptionalValue::Some(signature) => {
+                let message = self.usage_attestation_message(sub_id, sub.next_payment_block, usage);
+                self.crypto().verify_ed25519(
+                    sub.client.as_managed_buffer(),
+                    &message,
+                    &signature,
+                );
+                true
+            },
+            OptionalValue::None => false,
+        };
+
+        self.pending_usage(sub_id).set(&UsageReport {
+            cycle_block: sub.next_payment_block,
+            usage,
+            reported_block: self.blockchain().get_block_nonce(),
+            attested,
+        });
+        self.last_usage_report_cycle(sub_id).set(sub.next_payment_block);
+    }
+
+    /// Discards an unattested usage report while it is still inside the
+    /// dispute window. The provider may resubmit it with an attestation.
+    #[endpoint(disputeUsage)]
+    fn dispute_usage(&self, sub_id: u64) {
+        let caller = self.blockchain().get_caller();
+        let sub = self.subscriptions(sub_id).get();
+        require!(caller == sub.client, "Only subscriber can dispute usage");
+        require!(
+            !self.pending_usage(sub_id).is_empty(),
+            "No pending usage report"
+        );
 
+        let report = self.pending_usage(sub_id).get();
+        require!(!report.attested, "Usage report was attested");
         let current_block = self.blockchain().get_block_nonce();
         require!(
-            current_block >= sub.next_payment_block,
-            "Payment cycle not reached yet"
+            current_block < report.reported_block + self.usage_dispute_window().get(),
+            "Dispute window has passed"
+        );
+
+        self.pending_usage(sub_id).clear();
+        self.last_usage_report_cycle(sub_id).clear();
+    }
+
+    // --- DISPUTES ---
+
+    #[endpoint(openDispute)]
+    fn open_dispute(&self, sub_id: u64) {
+        let caller = self.blockchain().get_caller();
+        let mut sub = self.subscriptions(sub_id).get();
+        require!(caller == sub.client, "Only subscriber can open a dispute");
+        require!(
+            !self.arbiter_for(sub.service_id).is_zero(),
+            "No arbiter appointed"
         );
 
-        if sub.remaining_balance < sub.amount_per_cycle {
-            if sub.remaining_balance > 0 {
-                self.send().direct(
-                    &sub.client,
-                    &sub.token_identifier,
-                    0,
-                    &sub.remaining_balance,
-                );
-                sub.remaining_balance = BigUint::zero();
-            }
-            sub.status = STATUS_CANCELLED_INSUFFICIENT_FUNDS;
-            sub.cancel_effective_block = current_block;
-            self.subscriptions(sub_id).set(&sub);
-            self.cancel_requested_by_is_set(sub_id).set(false);
-            self.cancel_requested_by(sub_id).clear();
-            return;
+        let current_block = self.blockchain().get_block_nonce();
+        self.transition(&mut sub, SubscriptionStatus::Disputed, Actor::Subscriber);
+        self.subscriptions(sub_id).set(&sub);
+        self.disputes(sub_id).set(&Dispute {
+            opened_block: current_block,
+            deadline_block: current_block + self.dispute_timeout().get(),
+            escrow: self.held_payment(sub_id).take(),
+        });
+    }
+
+    /// Pays `provider_share_bps` of the escrow to the provider, refunds
+    /// the rest to the subscriber and unfreezes the subscription.
+    #[endpoint(resolveDispute)]
+    fn resolve_dispute(&self, sub_id: u64, provider_share_bps: u64) {
+        let caller = self.blockchain().get_caller();
+        let sub = self.subscriptions(sub_id).get();
+        require!(
+            sub.status == SubscriptionStatus::Disputed,
+            "Subscription not disputed"
+        );
+        require!(
+            caller == self.arbiter_for(sub.service_id),
+            "Only arbiter can resolve"
+        );
+        require!(provider_share_bps <= BPS_DENOMINATOR, "Invalid share");
+
+        self.settle_dispute(sub, provider_share_bps, Actor::Arbiter);
+    }
+
+    /// Settles a dispute the arbiter did not resolve in time,
+    /// using the default provider share.
+    #[endpoint(expireDispute)]
+    fn expire_dispute(&self, sub_id: u64) {
+        let sub = self.subscriptions(sub_id).get();
+        require!(
+            sub.status == SubscriptionStatus::Disputed,
+            "Subscription not disputed"
+        );
+        let dispute = self.disputes(sub_id).get();
+        require!(
+            self.blockchain().get_block_nonce() >= dispute.deadline_block,
+            "Dispute not expired yet"
+        );
+
+        let provider_share_bps = self.dispute_default_provider_share().get();
+        self.settle_dispute(sub, provider_share_bps, Actor::Scheduler);
+    }
+
+    // --- SCHEDULER: PAYMENTS ---
+
+    /// Each cycle payment is held until the next one is charged, and only
+    /// then released to the provider. Cycle payments of a disputed
+    /// subscription are kept in the dispute escrow until it is resolved.
+    #[endpoint(triggerPayment)]
+    fn trigger_payment(&self, sub_id: u64) {
+        self.require_not_migrating();
+        let mut sub = self.subscriptions(sub_id).get();
+        let is_disputed = sub.status == SubscriptionStatus::Disputed;
+        require!(
+            sub.status == SubscriptionStatus::Active || is_disputed,
+            "Subscription not active"
+        );
+
+        let current_block = self.blockchain().get_block_nonce();
+        require!(
+            current_block >= sub.next_payment_block,
+            "Payment cycle not reached yet"
+        );
+
+        let cycle_block = sub.next_payment_block;
+        self.apply_price_changes(&mut sub, cycle_block);
+        let usage_settled = self.usage_settled(sub_id, current_block);
+        let mut usage_charge = self.settled_usage_charge(sub_id, current_block);
+        let cycle_charge = sub.cycle_charge();
+
+        // Only the base fee can end the subscription on a limit; usage is
+        // capped to whatever the spending limit leaves after it.
+        let limit_reached = self.spending_limit_reached(&sub, &cycle_charge, current_block);
+        if !limit_reached && sub.max_total_spend > 0 {
+            let allowance = &sub.max_total_spend - &sub.total_spent - &cycle_charge;
+            usage_charge = core::cmp::min(usage_charge, allowance);
+        }
+        let amount_due = &cycle_charge + &usage_charge;
+        if is_disputed {
+            require!(
+                !limit_reached && sub.remaining_balance >= amount_due,
+                "Dispute must be resolved first"
+            );
+        }
+
+        if limit_reached {
+            self.charge_final_usage(&mut sub, &usage_charge);
+            self.close_with_refund(&mut sub, SubscriptionStatus::Completed, current_block);
+            return;
+        }
+
+        if sub.remaining_balance < amount_due {
+            let collected = self.charge_final_usage(&mut sub, &usage_charge);
+            self.record_settlement(&sub, &(&amount_due - &collected), SettlementKind::Shortfall);
+            self.close_with_refund(
+                &mut sub,
+                SubscriptionStatus::CancelledInsufficientFunds,
+                current_block,
+            );
+            return;
+        }
+
+        if usage_settled {
+            self.pending_usage(sub_id).clear();
+        }
+        if is_disputed {
+            self.disputes(sub_id).update(|dispute| dispute.escrow += &amount_due);
+        } else {
+            self.hold_cycle_payment(&sub, &amount_due);
+        }
+        self.record_settlement(&sub, &amount_due, SettlementKind::Cycle);
+        sub.remaining_balance -= &amount_due;
+        sub.total_spent += &amount_due;
+        sub.cycles_paid += 1;
+        sub.last_payment_block = current_block;
+        sub.next_payment_block = current_block + sub.frequency_in_blocks;
+        self.subscriptions(sub_id).set(&sub);
+        self.notify_hook(&sub, HOOK_SUBSCRIPTION_RENEWED, sub.next_payment_block);
+    }
+
+    #[endpoint(finalizeCancellation)]
+    fn finalize_cancellation(&self, sub_id: u64) {
+        self.require_not_migrating();
+        let mut sub = self.subscriptions(sub_id).get();
+        require!(sub.status.is_pending_cancel(), "Subscription not pending cancel");
+
+        let current_block = self.blockchain().get_block_nonce();
+        require!(
+            current_block >= sub.cancel_effective_block,
+            "Cancellation not effective yet"
+        );
+
+        let final_status = if sub.status == SubscriptionStatus::PendingUserCancel {
+            SubscriptionStatus::CancelledByUser
+        } else {
+            SubscriptionStatus::CancelledByProvider
+        };
+        self.transition(&mut sub, final_status, Actor::Scheduler);
+        self.release_held_payment(&sub);
+
+        if !self.pending_usage(sub_id).is_empty() {
+            require!(
+                self.usage_settled(sub_id, current_block),
+                "Usage report still in dispute window"
+            );
+            let usage_charge = self.settled_usage_charge(sub_id, current_block);
+            self.charge_final_usage(&mut sub, &usage_charge);
+            self.pending_usage(sub_id).clear();
+        }
+
+        if sub.escrowed_termination_fee > 0 {
+            self.pay_provider(&sub, &sub.escrowed_termination_fee);
+            self.record_settlement(&sub, &sub.escrowed_termination_fee, SettlementKind::Fee);
+            sub.total_spent += &sub.escrowed_termination_fee;
+            sub.escrowed_termination_fee = BigUint::zero();
+        }
+
+        if sub.remaining_balance > 0 {
+            self.refund_client(&sub, &sub.remaining_balance);
+            sub.remaining_balance = BigUint::zero();
+        }
+
+        self.subscriptions(sub_id).set(&sub);
+        self.cancel_requested_by_is_set(sub_id).set(false);
+        self.cancel_requested_by(sub_id).clear();
+        self.remove_from_active_sets(&sub);
+        self.notify_hook(&sub, HOOK_SUBSCRIPTION_ENDED, sub.status);
+    }
+
+    // --- STORAGE PRUNING ---
+
+    /// Replaces a terminated subscription with a compact summary and clears
+    /// its per-subscription storage, keeping the bounded payment history.
+    /// MultiversX does not refund gas for freed storage, but the state no
+    /// longer has to be carried around. A subscription the provider approved
+    /// for revival is kept until the approval is withdrawn.
+    #[endpoint(archiveSubscription)]
+    fn archive_subscription(&self, sub_id: u64) {
+        require!(!self.subscriptions(sub_id).is_empty(), "Subscription not found");
+        let caller = self.blockchain().get_caller();
+        let sub = self.subscriptions(sub_id).get();
+        require!(
+            caller == sub.client
+                || caller == sub.vendor
+                || caller == self.blockchain().get_owner_address(),
+            "Only subscriber, provider or owner can archive"
+        );
+        require!(
+            sub.status.is_terminal(),
+            "Subscription not terminated"
+        );
+        require!(
+            !self.revival_approved(sub_id).get(),
+            "Subscription approved for revival"
+        );
+        require!(
+            sub.remaining_balance == 0 && sub.escrowed_termination_fee == 0,
+            "Subscription still holds funds"
+        );
+
+        let mut summary = self.summarize_subscription(&sub);
+        summary.archived = true;
+        self.archived_subscriptions(sub_id).set(&summary);
+
+        self.subscriptions(sub_id).clear();
+        self.subscription_metering(sub_id).clear();
+        self.pending_usage(sub_id).clear();
+        self.last_usage_report_cycle(sub_id).clear();
+        self.disputes(sub_id).clear();
+        self.cancel_requested_by(sub_id).clear();
+        self.cancel_requested_by_is_set(sub_id).clear();
+        self.revival_approved(sub_id).clear();
+        self.pending_transfer(sub_id).clear();
+        self.clear_subscription_members(sub_id);
+    }
+
+    // --- HELPERS ---
+
+    fn require_service_provider(&self, service_id: u64) -> ManagedAddress {
+        let caller = self.blockchain().get_caller();
+        let service = self.services(service_id).get();
+        require!(caller == service.provider, "Only provider can manage service");
+        caller
+    }
+
+    /// The service with every price change that took effect applied,
+    /// and the resulting price epoch.
+    fn effective_service_and_epoch(&self, service_id: u64) -> (Service<Self::Api>, u64) {
+        let mut service = self.services(service_id).get();
+        let current_block = self.blockchain().get_block_nonce();
+        let mut epoch = self.service_price_epoch(service_id).get();
+        while epoch < self.price_change_count(service_id).get() {
+            let change = self.price_changes(service_id, epoch + 1).get();
+            if change.effective_block > current_block {
+                break;
+            }
+            service.amount_per_cycle = change.amount_per_cycle;
+            service.frequency_in_blocks = change.frequency_in_blocks;
+            epoch += 1;
+        }
+        (service, epoch)
+    }
+
+    fn effective_service(&self, service_id: u64) -> Service<Self::Api> {
+        self.effective_service_and_epoch(service_id).0
+    }
+
+    fn sync_service_price(&self, service_id: u64) -> Service<Self::Api> {
+        let (service, epoch) = self.effective_service_and_epoch(service_id);
+        self.service_price_epoch(service_id).set(epoch);
+        self.services(service_id).set(&service);
+        service
+    }
+
+    /// Moves the subscription to every price change effective by `cycle_block`.
+    fn apply_price_changes(&self, sub: &mut Subscription<Self::Api>, cycle_block: u64) {
+        while sub.price_epoch < self.price_change_count(sub.service_id).get() {
+            let change = self.price_changes(sub.service_id, sub.price_epoch + 1).get();
+            if change.effective_block > cycle_block {
+                break;
+            }
+            sub.amount_per_cycle = change.amount_per_cycle;
+            sub.frequency_in_blocks = change.frequency_in_blocks;
+            sub.price_epoch += 1;
+        }
+    }
+
+    fn add_service(&self, mut service: Service<Self::Api>) -> u64 {
+        let bond = self.call_value().egld().clone_value();
+        require!(bond == self.service_bond_amount().get(), "Invalid service bond");
+
+        let service_id = self.last_service_id().get() + 1;
+        self.last_service_id().set(service_id);
+        service.id = service_id;
+
+        self.services(service_id).set(&service);
+        self.service_ids().push(&service_id);
+        self.service_listing_index(service_id).set(self.service_ids().len());
+        self.provider_services(&service.provider).push(&service_id);
+        self.index_service(&service);
+        if bond > 0 {
+            self.service_bond(service_id).set(&bond);
+            self.add_liability(&EgldOrEsdtTokenIdentifier::egld(), 0, &bond);
+        }
+        service_id
+    }
+
+    /// Takes the service out of `serviceIds`, moving the last listed
+    /// service into its place; it can no longer be reactivated.
+    fn remove_from_listing(&self, service_id: u64) {
+        if self.service_delisted(service_id).get() {
+            return;
+        }
+        self.service_delisted(service_id).set(true);
+        let index = self.service_listing_index(service_id).take();
+        if index == 0 {
+            return;
+        }
+        let mut service_ids = self.service_ids();
+        let last_index = service_ids.len();
+        if index != last_index {
+            let moved_id = service_ids.get(last_index);
+            self.service_listing_index(moved_id).set(index);
+        }
+        service_ids.swap_remove(index);
+    }
+
+    fn require_not_archived(&self, sub_id: u64) {
+        require!(
+            self.archived_subscriptions(sub_id).is_empty(),
+            "Subscription archived"
+        );
+    }
+
+    fn require_not_migrating(&self) {
+        require!(self.schema_migration().is_empty(), "Migration in progress");
+    }
+
+    fn require_moderator(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.blockchain().get_owner_address() || self.moderators().contains(&caller),
+            "Only owner or moderator"
+        );
+    }
+
+    fn service_is_full(&self, service_id: u64) -> bool {
+        if self.service_metadata(service_id).is_empty() {
+            return false;
+        }
+        let max_subscribers = self.service_metadata(service_id).get().max_subscribers;
+        max_subscribers > 0
+            && self.service_active_subscriptions(service_id).len() as u64 >= max_subscribers
+    }
+
+    /// Discovery indexes only ever hold active services.
+    fn index_service(&self, service: &Service<Self::Api>) {
+        if !service.active {
+            return;
+        }
+        self.token_services(&service.token_identifier).insert(service.id);
+        if self.service_metadata(service.id).is_empty() {
+            return;
+        }
+        let metadata = self.service_metadata(service.id).get();
+        self.category_services(metadata.category_id).insert(service.id);
+        for tag in metadata.tags.iter() {
+            self.tag_services(&tag).insert(service.id);
+        }
+    }
+
+    fn unindex_service(&self, service: &Service<Self::Api>) {
+        self.token_services(&service.token_identifier).swap_remove(&service.id);
+        if self.service_metadata(service.id).is_empty() {
+            return;
+        }
+        let metadata = self.service_metadata(service.id).get();
+        self.category_services(metadata.category_id).swap_remove(&service.id);
+        for tag in metadata.tags.iter() {
+            self.tag_services(&tag).swap_remove(&service.id);
+        }
+    }
+
+    fn indexed_services_page(
+        &self,
+        ids: &UnorderedSetMapper<u64>,
+        offset: usize,
+        limit: usize,
+    ) -> MultiValueEncoded<Service<Self::Api>> {
+        let mut out = MultiValueEncoded::new();
+        let limit = core::cmp::min(limit, MAX_PAGE_SIZE);
+        let end = core::cmp::min(ids.len(), offset.saturating_add(limit));
+        for index in offset..end {
+            out.push(self.effective_service(ids.get_by_index(index + 1)));
+        }
+        out
+    }
+
+    fn has_service_access(&self, service_id: u64, address: &ManagedAddress) -> bool {
+        match self.service_access_mode(service_id).get() {
+            AccessMode::Public => true,
+            AccessMode::Allowlist => self.service_allowlist(service_id).contains(address),
+            AccessMode::Blocklist => !self.service_blocklist(service_id).contains(address),
+        }
+    }
+
+    /// Whether the address can use a service it reaches through a bundle:
+    /// the service must be active and its access list must admit them.
+    fn bundled_service_open_to(&self, service_id: u64, address: &ManagedAddress) -> bool {
+        self.services(service_id).get().active && self.has_service_access(service_id, address)
+    }
+
+    fn bundle_open_to(&self, bundle_id: u64, address: &ManagedAddress) -> bool {
+        self.bundle_services(bundle_id)
+            .iter()
+            .all(|service_id| self.bundled_service_open_to(service_id, address))
+    }
+
+    fn process_deactivation_batch(&self, service: &Service<Self::Api>) {
+        let mut progress = self.service_deactivation(service.id).get();
+        if progress.completed {
+            return;
+        }
+
+        let ids = self.service_subscriptions(service.id);
+        let end = core::cmp::min(ids.len() + 1, progress.next_index + DEACTIVATION_BATCH_SIZE);
+        for index in progress.next_index..end {
+            let sub_id = ids.get(index);
+            if self.subscriptions(sub_id).is_empty() {
+                continue;
+            }
+            let sub = self.subscriptions(sub_id).get();
+            match progress.mode {
+                DeactivationMode::StopRenewals => {
+                    if sub.status == SubscriptionStatus::Active {
+                        self.request_provider_cancel(sub, &service.provider);
+                    }
+                },
+                DeactivationMode::TerminateNow => {
+                    if !sub.status.is_terminal() {
+                        self.terminate_now(sub);
+                    }
+                },
+                DeactivationMode::StopNewSignups => {},
+            }
+        }
+        progress.next_index = end;
+        progress.completed = end > ids.len();
+        self.service_deactivation(service.id).set(&progress);
+    }
+
+    /// Ends the subscription at once: the subscriber gets back its balance,
+    /// any escrowed fee, and the unused part of the current cycle out of the
+    /// payment held for it. A dispute escrow goes back whole, as the
+    /// provider ends the service before the arbiter rules on it.
+    fn terminate_now(&self, mut sub: Subscription<Self::Api>) {
+        let current_block = self.blockchain().get_block_nonce();
+        self.transition(&mut sub, SubscriptionStatus::CancelledByProvider, Actor::Provider);
+        if !self.disputes(sub.id).is_empty() {
+            let escrow = self.disputes(sub.id).take().escrow;
+            sub.remaining_balance += &escrow;
+            sub.total_spent -= &escrow;
+        } else {
+            self.return_unused_held_payment(&mut sub, current_block);
+        }
+        sub.remaining_balance += &sub.escrowed_termination_fee;
+        sub.escrowed_termination_fee = BigUint::zero();
+        if sub.remaining_balance > 0 {
+            self.refund_client(&sub, &sub.remaining_balance);
+            sub.remaining_balance = BigUint::zero();
+        }
+        sub.cancel_effective_block = current_block;
+        self.subscriptions(sub.id).set(&sub);
+        self.cancel_requested_by_is_set(sub.id).set(false);
+        self.cancel_requested_by(sub.id).clear();
+        self.pending_usage(sub.id).clear();
+        self.remove_from_active_sets(&sub);
+        self.notify_hook(&sub, HOOK_SUBSCRIPTION_ENDED, sub.status);
+    }
+
+    /// Moves the unused part of the current cycle from the held payment back
+    /// into the balance and pays the provider the rest. A cycle already paid
+    /// out, such as one an arbiter ruled on, is not refunded.
+    fn return_unused_held_payment(&self, sub: &mut Subscription<Self::Api>, current_block: u64) {
+        let held = self.held_payment(sub.id).take();
+        let unused_blocks = core::cmp::min(
+            sub.next_payment_block.saturating_sub(current_block),
+            sub.frequency_in_blocks,
+        );
+        let prorated = sub.cycle_charge() * unused_blocks / sub.frequency_in_blocks;
+        let unused = core::cmp::min(prorated, held.clone());
+        if held > unused {
+            self.pay_provider(sub, &(&held - &unused));
+        }
+        sub.remaining_balance += &unused;
+        sub.total_spent -= &unused;
+    }
+
+    fn stops_renewals(&self, service_id: u64) -> bool {
+        !self.service_deactivation(service_id).is_empty()
+            && self.service_deactivation(service_id).get().mode != DeactivationMode::StopNewSignups
+    }
+
+    fn request_provider_cancel(&self, mut sub: Subscription<Self::Api>, provider: &ManagedAddress) {
+        self.transition(&mut sub, SubscriptionStatus::PendingProviderCancel, Actor::Provider);
+        sub.cancel_effective_block = sub.next_payment_block;
+        self.subscriptions(sub.id).set(&sub);
+        self.cancel_requested_by(sub.id).set(provider);
+        self.cancel_requested_by_is_set(sub.id).set(true);
+    }
+
+    fn transition(
+        &self,
+        sub: &mut Subscription<Self::Api>,
+        to: SubscriptionStatus,
+        actor: Actor,
+    ) {
+        require!(
+            is_allowed_transition(sub.status, to, actor),
+            "Invalid status transition"
+        );
+        let from = sub.status;
+        sub.status = to;
+        if from == SubscriptionStatus::Active {
+            // Only active subscriptions can be transferred.
+            self.pending_transfer(sub.id).clear();
+        }
+        self.record_status_change(sub, from, to);
+    }
+
+    fn record_status_change(
+        &self,
+        sub: &Subscription<Self::Api>,
+        from: SubscriptionStatus,
+        to: SubscriptionStatus,
+    ) {
+        let update = |stats: &mut SubscriberStats| {
+            if from == SubscriptionStatus::None {
+                stats.lifetime_subscriptions += 1;
+            }
+            if from == SubscriptionStatus::None || from.is_cancelled() {
+                stats.active_subscribers += 1;
+            }
+            if to.is_terminal() {
+                stats.active_subscribers -= 1;
+            }
+            match to {
+                SubscriptionStatus::CancelledByUser => stats.cancelled_by_user += 1,
+                SubscriptionStatus::CancelledByProvider => stats.cancelled_by_provider += 1,
+                SubscriptionStatus::CancelledInsufficientFunds => {
+                    stats.cancelled_insufficient_funds += 1
+                },
+                SubscriptionStatus::Completed => stats.completed += 1,
+                _ => {},
+            }
+        };
+        self.update_stats(&self.service_stats(sub.service_id), update);
+        self.update_stats(&self.provider_stats(&sub.vendor), update);
+    }
+
+    fn update_stats<F: Fn(&mut SubscriberStats)>(
+        &self,
+        mapper: &SingleValueMapper<SubscriberStats>,
+        update: F,
+    ) {
+        let mut stats = if mapper.is_empty() {
+            SubscriberStats::default()
+        } else {
+            mapper.get()
+        };
+        update(&mut stats);
+        mapper.set(&stats);
+    }
+
+    fn migrate_service(&self, service_id: u64) {
+        let legacy_mapper = self.legacy_services(service_id);
+        if legacy_mapper.is_empty() {
+            return;
+        }
+        let legacy = legacy_mapper.get();
+        let service = Service {
+            id: legacy.id,
+            provider: legacy.provider,
+            name: legacy.name,
+            description: legacy.description,
+            token_identifier: legacy.token_identifier,
+            token_nonce: 0,
+            amount_per_cycle: legacy.amount_per_cycle,
+            frequency_in_blocks: legacy.frequency_in_blocks,
+            active: legacy.active,
+        };
+        self.services(service_id).set(&service);
+        // Version 1 listed every service, in id order, and never delisted.
+        self.service_listing_index(service_id).set(service_id as usize);
+        self.index_service(&service);
+    }
+
+    /// Version 1 paid each cycle straight to the provider and kept no
+    /// totals, so only the cycle in progress, paid on subscribing or on the
+    /// latest charge, is counted as spent and collected.
+    fn migrate_subscription(&self, sub_id: u64) {
+        let legacy_mapper = self.legacy_subscriptions(sub_id);
+        if legacy_mapper.is_empty() {
+            return;
+        }
+        let legacy = legacy_mapper.get();
+        let cycle_paid = legacy.amount_per_cycle.clone();
+        let sub = Subscription {
+            id: legacy.id,
+            service_id: legacy.service_id,
+            client: legacy.client,
+            vendor: legacy.vendor,
+            token_identifier: legacy.token_identifier,
+            token_nonce: 0,
+            amount_per_cycle: legacy.amount_per_cycle,
+            frequency_in_blocks: legacy.frequency_in_blocks,
+            remaining_balance: legacy.remaining_balance,
+            last_payment_block: legacy.last_payment_block,
+            next_payment_block: legacy.next_payment_block,
+            status: legacy.status,
+            cancel_effective_block: legacy.cancel_effective_block,
+            max_total_spend: BigUint::zero(),
+            max_cycles: 0,
+            end_block: 0,
+            total_spent: cycle_paid.clone(),
+            cycles_paid: 1,
+            commitment_end_block: 0,
+            early_termination_fee: BigUint::zero(),
+            escrowed_termination_fee: BigUint::zero(),
+            price_epoch: 0,
+            seats: 1,
+        };
+        self.subscriptions(sub_id).set(&sub);
+        self.service_total_collected(sub.service_id).update(|total| *total += &cycle_paid);
+        self.provider_total_collected(&sub.vendor, &sub.token_identifier)
+            .update(|total| *total += &cycle_paid);
+
+        self.record_status_change(&sub, SubscriptionStatus::None, SubscriptionStatus::Active);
+        if sub.status != SubscriptionStatus::Active {
+            self.record_status_change(&sub, SubscriptionStatus::Active, sub.status);
+        }
+        if !sub.status.is_terminal() {
+            self.user_active_subscriptions(&sub.client).insert(sub_id);
+            self.provider_active_subscriptions(&sub.vendor).insert(sub_id);
+            self.service_active_subscriptions(sub.service_id).insert(sub_id);
+        }
+        if sub.remaining_balance > 0 {
+            self.record_deposit(&sub, &sub.remaining_balance);
+        }
+    }
+
+    /// Funds received from the subscriber; held by the contract until paid
+    /// out or refunded.
+    fn record_deposit(&self, sub: &Subscription<Self::Api>, amount: &BigUint) {
+        self.add_liability(&sub.token_identifier, sub.token_nonce, amount);
+        self.service_committed_escrow(sub.service_id).update(|escrow| *escrow += amount);
+        self.provider_committed_escrow(&sub.vendor, &sub.token_identifier)
+            .update(|escrow| *escrow += amount);
+    }
+
+    fn apply_top_up(
+        &self,
+        caller: &ManagedAddress,
+        sub_id: u64,
+        payment: &EgldOrEsdtTokenPayment,
+    ) {
+        self.require_not_migrating();
+        let mut sub = self.subscriptions(sub_id).get();
+        require!(caller == &sub.client, "Only subscriber can top up");
+        require!(!sub.status.is_terminal(), "Subscription is terminated");
+        require!(
+            payment.token_identifier == sub.token_identifier
+                && payment.token_nonce == sub.token_nonce,
+            "Invalid payment token"
+        );
+        require!(payment.amount > 0, "Top up amount must be > 0");
+
+        self.record_deposit(&sub, &payment.amount);
+        sub.remaining_balance += &payment.amount;
+        self.subscriptions(sub_id).set(&sub);
+    }
+
+    /// The latest cycle payment stays in the contract until the next one is
+    /// charged, so that a dispute can still keep it from the provider.
+    fn hold_cycle_payment(&self, sub: &Subscription<Self::Api>, amount: &BigUint) {
+        self.release_held_payment(sub);
+        self.held_payment(sub.id).set(amount);
+    }
+
+    fn release_held_payment(&self, sub: &Subscription<Self::Api>) {
+        let held = self.held_payment(sub.id).take();
+        if held > 0 {
+            self.pay_provider(sub, &held);
+        }
+    }
+
+    fn pay_provider(&self, sub: &Subscription<Self::Api>, amount: &BigUint) {
+        self.send().direct(&sub.vendor, &sub.token_identifier, sub.token_nonce, amount);
+        self.release_escrow(sub, amount);
+        self.service_total_collected(sub.service_id).update(|total| *total += amount);
+        self.provider_total_collected(&sub.vendor, &sub.token_identifier)
+            .update(|total| *total += amount);
+        self.issue_invoice(sub, amount);
+    }
+
+    fn refund_client(&self, sub: &Subscription<Self::Api>, amount: &BigUint) {
+        self.send().direct(&sub.client, &sub.token_identifier, sub.token_nonce, amount);
+        self.release_escrow(sub, amount);
+        self.record_settlement(sub, amount, SettlementKind::Refund);
+    }
+
+    /// Keeps the last `PAYMENT_HISTORY_SIZE` settlements, overwriting the oldest.
+    /// Only movements of the subscriber's funds are recorded; payouts to the
+    /// provider are kept as invoices instead.
+    fn record_settlement(&self, sub: &Subscription<Self::Api>, amount: &BigUint, kind: SettlementKind) {
+        let count = self.settlement_count(sub.id).get();
+        self.settlements(sub.id, count % PAYMENT_HISTORY_SIZE).set(&Settlement {
+            block: self.blockchain().get_block_nonce(),
+            timestamp: self.blockchain().get_block_timestamp(),
+            amount: amount.clone(),
+            kind,
+        });
+        self.settlement_count(sub.id).set(count + 1);
+    }
+
+    /// Each payout gets the provider's next invoice number; charges held in
+    /// the contract or in a dispute escrow are not invoiced until paid out.
+    fn issue_invoice(&self, sub: &Subscription<Self::Api>, amount: &BigUint) {
+        let number = self.last_invoice_number(&sub.vendor).get() + 1;
+        self.last_invoice_number(&sub.vendor).set(number);
+        self.invoices(&sub.vendor, number).set(&Invoice {
+            subscription_id: sub.id,
+            service_id: sub.service_id,
+            amount: amount.clone(),
+            token_identifier: sub.token_identifier.clone(),
+            token_nonce: sub.token_nonce,
+            block: self.blockchain().get_block_nonce(),
+        });
+    }
+
+    fn release_escrow(&self, sub: &Subscription<Self::Api>, amount: &BigUint) {
+        self.remove_liability(&sub.token_identifier, sub.token_nonce, amount);
+        self.service_committed_escrow(sub.service_id).update(|escrow| *escrow -= amount);
+        self.provider_committed_escrow(&sub.vendor, &sub.token_identifier)
+            .update(|escrow| *escrow -= amount);
+    }
+
+    fn add_liability(&self, token: &EgldOrEsdtTokenIdentifier, nonce: u64, amount: &BigUint) {
+        self.total_liabilities(token, nonce).update(|total| *total += amount);
+    }
+
+    fn remove_liability(&self, token: &EgldOrEsdtTokenIdentifier, nonce: u64, amount: &BigUint) {
+        self.total_liabilities(token, nonce).update(|total| {
+            require!(*total >= *amount, "Liabilities out of balance");
+            *total -= amount;
+        });
+    }
+
+    fn spending_limit_reached(
+        &self,
+        sub: &Subscription<Self::Api>,
+        amount_due: &BigUint,
+        current_block: u64,
+    ) -> bool {
+        if sub.max_cycles > 0 && sub.cycles_paid >= sub.max_cycles {
+            return true;
+        }
+        if sub.end_block > 0 && current_block >= sub.end_block {
+            return true;
+        }
+        sub.max_total_spend > 0 && &sub.total_spent + amount_due > sub.max_total_spend
+    }
+
+    /// Charges settled usage of a subscription that is ending, capped by its
+    /// balance and spending limit. Returns the amount collected.
+    fn charge_final_usage(&self, sub: &mut Subscription<Self::Api>, usage_charge: &BigUint) -> BigUint {
+        let mut charge = core::cmp::min(usage_charge.clone(), sub.remaining_balance.clone());
+        if sub.max_total_spend > 0 {
+            let allowance = if sub.max_total_spend > sub.total_spent {
+                &sub.max_total_spend - &sub.total_spent
+            } else {
+                BigUint::zero()
+            };
+            charge = core::cmp::min(charge, allowance);
+        }
+        if charge > 0 {
+            self.pay_provider(sub, &charge);
+            self.record_settlement(sub, &charge, SettlementKind::Cycle);
+            sub.remaining_balance -= &charge;
+            sub.total_spent += &charge;
+        }
+        charge
+    }
+
+    /// Moves the subscription into a terminal status, refunding whatever
+    /// is left of its balance to the client.
+    fn close_with_refund(
+        &self,
+        sub: &mut Subscription<Self::Api>,
+        status: SubscriptionStatus,
+        current_block: u64,
+    ) {
+        self.release_held_payment(sub);
+        if sub.remaining_balance > 0 {
+            self.refund_client(sub, &sub.remaining_balance);
+            sub.remaining_balance = BigUint::zero();
+        }
+        self.transition(sub, status, Actor::Scheduler);
+        sub.cancel_effective_block = current_block;
+        self.subscriptions(sub.id).set(&*sub);
+        self.cancel_requested_by_is_set(sub.id).set(false);
+        self.cancel_requested_by(sub.id).clear();
+        self.pending_usage(sub.id).clear();
+        self.remove_from_active_sets(sub);
+        self.notify_hook(sub, HOOK_SUBSCRIPTION_ENDED, sub.status);
+    }
+
+    /// Calls the service hook, if any. Same-shard hooks are called
+    /// synchronously, others through a promise. A failing hook is only
+    /// counted; it never reverts the payment that triggered it.
+    fn notify_hook<T: TopEncode>(
+        &self,
+        sub: &Subscription<Self::Api>,
+        function: &'static str,
+        detail: T,
+    ) {
+        if self.service_hook(sub.service_id).is_empty() {
+            return;
+        }
+        let hook = self.service_hook(sub.service_id).get();
+        let sc_address = self.blockchain().get_sc_address();
+        let same_shard = self.blockchain().get_shard_of_address(&hook)
+            == self.blockchain().get_shard_of_address(&sc_address);
+        let gas_needed = if same_shard {
+            HOOK_GAS_LIMIT + HOOK_GAS_RESERVE
+        } else {
+            HOOK_GAS_LIMIT + HOOK_CALLBACK_GAS + HOOK_GAS_RESERVE
+        };
+        if self.blockchain().get_gas_left() < gas_needed {
+            self.hook_failures(sub.service_id).update(|failures| *failures += 1);
+            return;
+        }
+
+        let call = self
+            .tx()
+            .to(&hook)
+            .gas(HOOK_GAS_LIMIT)
+            .raw_call(function)
+            .argument(&sub.id)
+            .argument(&sub.client)
+            .argument(&detail);
+        if same_shard {
+            let result = call.returns(ReturnsHandledOrError::new()).sync_call_fallible();
+            if result.is_err() {
+                self.hook_failures(sub.service_id).update(|failures| *failures += 1);
+            }
+        } else {
+            call.callback(self.callbacks().hook_callback(sub.service_id))
+                .gas_for_callback(HOOK_CALLBACK_GAS)
+                .register_promise();
+        }
+    }
+
+    #[promises_callback]
+    fn hook_callback(&self, service_id: u64, #[call_result] result: ManagedAsyncCallResult<IgnoreValue>) {
+        if let ManagedAsyncCallResult::Err(_) = result {
+            self.hook_failures(service_id).update(|failures| *failures += 1);
+        }
+    }
+
+    /// Covers subscriptions the address pays for and team subscriptions
+    /// it is a member of. A bundle only covers its services that are
+    /// active and open to the address.
+    fn access_until(&self, address: &ManagedAddress, service_id: u64) -> u64 {
+        let mut until = 0;
+        let owned = self.user_active_subscriptions(address);
+        let memberships = self.member_subscriptions(address);
+        for sub_id in owned.iter().chain(memberships.iter()) {
+            if self.subscriptions(sub_id).is_empty() {
+                continue;
+            }
+            let sub = self.subscriptions(sub_id).get();
+            if sub.status.is_terminal() {
+                continue;
+            }
+            let covers_service = sub.service_id == service_id
+                || (self.bundle_services(sub.service_id).contains(&service_id)
+                    && self.bundled_service_open_to(service_id, address));
+            if covers_service && sub.next_payment_block > until {
+                until = sub.next_payment_block;
+            }
+        }
+        until
+    }
+
+    fn clear_subscription_members(&self, sub_id: u64) {
+        for member in self.subscription_members(sub_id).iter() {
+            self.member_subscriptions(&member).swap_remove(&sub_id);
+        }
+        self.subscription_members(sub_id).clear();
+    }
+
+    fn remove_from_active_sets(&self, sub: &Subscription<Self::Api>) {
+        self.user_active_subscriptions(&sub.client).swap_remove(&sub.id);
+        self.provider_active_subscriptions(&sub.vendor).swap_remove(&sub.id);
+        self.service_active_subscriptions(sub.service_id).swap_remove(&sub.id);
+    }
+
+    fn summarize_subscription(&self, sub: &Subscription<Self::Api>) -> SubscriptionSummary<Self::Api> {
+        SubscriptionSummary {
+            id: sub.id,
+            service_id: sub.service_id,
+            client: sub.client.clone(),
+            vendor: sub.vendor.clone(),
+            token_identifier: sub.token_identifier.clone(),
+            status: sub.status,
+            total_spent: sub.total_spent.clone(),
+            cycles_paid: sub.cycles_paid,
+            cancel_effective_block: sub.cancel_effective_block,
+            archived: false,
+            token_nonce: sub.token_nonce,
+        }
+    }
+
+    fn subscription_summary(&self, sub_id: u64) -> SubscriptionSummary<Self::Api> {
+        if self.subscriptions(sub_id).is_empty() {
+            require!(
+                !self.archived_subscriptions(sub_id).is_empty(),
+                "Subscription not found"
+            );
+            return self.archived_subscriptions(sub_id).get();
+        }
+        self.summarize_subscription(&self.subscriptions(sub_id).get())
+    }
+
+    /// An archived subscription rebuilt from its summary; balances and
+    /// schedule fields read as zero.
+    fn archived_subscription(&self, sub_id: u64) -> Subscription<Self::Api> {
+        require!(
+            !self.archived_subscriptions(sub_id).is_empty(),
+            "Subscription not found"
+        );
+        let summary = self.archived_subscriptions(sub_id).get();
+        Subscription {
+            id: summary.id,
+            service_id: summary.service_id,
+            client: summary.client,
+            vendor: summary.vendor,
+            token_identifier: summary.token_identifier,
+            token_nonce: summary.token_nonce,
+            amount_per_cycle: BigUint::zero(),
+            frequency_in_blocks: 0,
+            remaining_balance: BigUint::zero(),
+            last_payment_block: 0,
+            next_payment_block: 0,
+            status: summary.status,
+            cancel_effective_block: summary.cancel_effective_block,
+            max_total_spend: BigUint::zero(),
+            max_cycles: 0,
+            end_block: 0,
+            total_spent: summary.total_spent,
+            cycles_paid: summary.cycles_paid,
+            commitment_end_block: 0,
+            early_termination_fee: BigUint::zero(),
+            escrowed_termination_fee: BigUint::zero(),
+            price_epoch: 0,
+            seats: 0,
+        }
+    }
+
+    /// The live subscription, or the archived one rebuilt from its summary.
+    fn live_or_archived_subscription(&self, sub_id: u64) -> Subscription<Self::Api> {
+        if self.subscriptions(sub_id).is_empty() {
+            return self.archived_subscription(sub_id);
+        }
+        self.subscriptions(sub_id).get()
+    }
+
+    /// A usage report settles once attested or once its dispute window
+    /// has passed, whatever the usage it reports.
+    fn usage_settled(&self, sub_id: u64, current_block: u64) -> bool {
+        if self.pending_usage(sub_id).is_empty() {
+            return false;
+        }
+        let report = self.pending_usage(sub_id).get();
+        report.attested || current_block >= report.reported_block + self.usage_dispute_window().get()
+    }
+
+    /// Metered charge of the pending usage report, or zero while the
+    /// report can still be disputed.
+    fn settled_usage_charge(&self, sub_id: u64, current_block: u64) -> BigUint {
+        if !self.usage_settled(sub_id, current_block) {
+            return BigUint::zero();
+        }
+
+        let report = self.pending_usage(sub_id).get();
+        let metering = self.subscription_metering(sub_id).get();
+        let billable_usage = core::cmp::min(report.usage, metering.usage_cap_per_cycle);
+        metering.unit_price * billable_usage
+    }
+
+    fn validate_governance_action(&self, action: &GovernanceAction<Self::Api>) {
+        match action {
+            GovernanceAction::SetServiceArbiter(service_id, _) => {
+                require!(!self.services(*service_id).is_empty(), "Service does not exist");
+            },
+            GovernanceAction::SetDisputeTimeout(blocks) => {
+                require!(*blocks > 0, "Timeout must be > 0");
+            },
+            GovernanceAction::SetDisputeDefaultProviderShare(provider_share_bps) => {
+                require!(*provider_share_bps <= BPS_DENOMINATOR, "Invalid share");
+            },
+            // Deposits not yet migrated are missing from the liabilities.
+            GovernanceAction::SweepSurplus(..) => self.require_not_migrating(),
+            _ => {},
         }
+    }
 
-        self.send().direct(
-            &sub.vendor,
-            &sub.token_identifier,
-            0,
-            &sub.amount_per_cycle,
-        );
-        sub.remaining_balance -= &sub.amount_per_cycle;
-        sub.last_payment_block = current_block;
-        sub.next_payment_block = current_block + sub.frequency_in_blocks;
-        self.subscriptions(sub_id).set(&sub);
+    fn apply_governance_action(&self, action: GovernanceAction<Self::Api>) {
+        match action {
+            GovernanceAction::SetUsageDisputeWindow(blocks) => self.usage_dispute_window().set(blocks),
+            GovernanceAction::SetArbiter(address) => self.arbiter().set(&address),
+            GovernanceAction::SetServiceArbiter(service_id, opt_address) => match opt_address {
+                Some(address) => self.service_arbiter(service_id).set(&address),
+                None => self.service_arbiter(service_id).clear(),
+            },
+            GovernanceAction::SetDisputeTimeout(blocks) => self.dispute_timeout().set(blocks),
+            GovernanceAction::SetDisputeDefaultProviderShare(provider_share_bps) => {
+                self.dispute_default_provider_share().set(provider_share_bps)
+            },
+            GovernanceAction::SetMinPriceNotice(blocks) => self.min_price_notice().set(blocks),
+            GovernanceAction::SetGovernanceDelay(blocks) => self.governance_delay().set(blocks),
+            GovernanceAction::SetServiceBond(amount) => self.service_bond_amount().set(&amount),
+            GovernanceAction::SetBondCooldown(blocks) => self.bond_cooldown().set(blocks),
+            GovernanceAction::SetModerator(address, true) => {
+                self.moderators().insert(address);
+            },
+            GovernanceAction::SetModerator(address, false) => {
+                self.moderators().swap_remove(&address);
+            },
+            GovernanceAction::SweepSurplus(token, nonce) => self.sweep_surplus(token, nonce),
+        }
     }
 
-    #[endpoint(finalizeCancellation)]
-    fn finalize_cancellation(&self, sub_id: u64) {
-        let mut sub = self.subscriptions(sub_id).get();
-        let is_pending = sub.status == STATUS_PENDING_USER_CANCEL
-            || sub.status == STATUS_PENDING_PROVIDER_CANCEL;
-        require!(is_pending, "Subscription not pending cancel");
+    fn sweep_surplus(&self, token: EgldOrEsdtTokenIdentifier, nonce: u64) {
+        let surplus = self.get_reconciliation(token.clone(), nonce).surplus;
+        require!(surplus > 0, "No surplus");
+        self.send().direct(&self.blockchain().get_owner_address(), &token, nonce, &surplus);
+    }
 
-        let current_block = self.blockchain().get_block_nonce();
-        require!(
-            current_block >= sub.cancel_effective_block,
-            "Cancellation not effective yet"
-        );
+    fn arbiter_for(&self, service_id: u64) -> ManagedAddress {
+        if !self.service_arbiter(service_id).is_empty() {
+            return self.service_arbiter(service_id).get();
+        }
+        if !self.arbiter().is_empty() {
+            return self.arbiter().get();
+        }
+        ManagedAddress::zero()
+    }
 
-        sub.status = if sub.status == STATUS_PENDING_USER_CANCEL {
-            STATUS_CANCELLED_BY_USER
-        } else {
-            STATUS_CANCELLED_BY_PROVIDER
-        };
+    fn settle_dispute(
+        &self,
+        mut sub: Subscription<Self::Api>,
+        provider_share_bps: u64,
+        actor: Actor,
+    ) {
+        let dispute = self.disputes(sub.id).take();
+        let provider_share = &dispute.escrow * provider_share_bps / BPS_DENOMINATOR;
+        let client_share = &dispute.escrow - &provider_share;
+
+        if provider_share > 0 {
+            self.pay_provider(&sub, &provider_share);
+        }
+        if client_share > 0 {
+            self.refund_client(&sub, &client_share);
+            sub.total_spent -= &client_share;
+        }
 
-        if sub.remaining_balance > 0 {
-            self.send().direct(
-                &sub.client,
-                &sub.token_identifier,
-                0,
-                &sub.remaining_balance,
-            );
-            sub.remaining_balance = BigUint::zero();
+        self.transition(&mut sub, SubscriptionStatus::Active, actor);
+        self.subscriptions(sub.id).set(&sub);
+        if self.stops_renewals(sub.service_id) {
+            let vendor = sub.vendor.clone();
+            self.request_provider_cancel(sub, &vendor);
         }
+    }
 
-        self.subscriptions(sub_id).set(&sub);
-        self.cancel_requested_by_is_set(sub_id).set(false);
-        self.cancel_requested_by(sub_id).clear();
+    fn services_page(
+        &self,
+        ids: &VecMapper<u64>,
+        offset: usize,
+        limit: usize,
+        active_only: bool,
+        token: Option<EgldOrEsdtTokenIdentifier>,
+    ) -> MultiValueEncoded<Service<Self::Api>> {
+        let mut out = MultiValueEncoded::new();
+        let limit = core::cmp::min(limit, MAX_PAGE_SIZE);
+        let end = core::cmp::min(ids.len(), offset.saturating_add(limit));
+        for index in offset..end {
+            let service = self.effective_service(ids.get(index + 1));
+            if active_only && !service.active {
+                continue;
+            }
+            if let Some(token) = &token {
+                if &service.token_identifier != token {
+                    continue;
+                }
+            }
+            out.push(service);
+        }
+        out
+    }
+
+    fn subscriptions_page(
+        &self,
+        ids: &VecMapper<u64>,
+        offset: usize,
+        limit: usize,
+        status: SubscriptionStatus,
+        token: Option<EgldOrEsdtTokenIdentifier>,
+    ) -> MultiValueEncoded<Subscription<Self::Api>> {
+        let mut out = MultiValueEncoded::new();
+        let limit = core::cmp::min(limit, MAX_PAGE_SIZE);
+        let end = core::cmp::min(ids.len(), offset.saturating_add(limit));
+        for index in offset..end {
+            let sub_id = ids.get(index + 1);
+            if self.subscriptions(sub_id).is_empty() {
+                continue;
+            }
+            let sub = self.subscriptions(sub_id).get();
+            if status != SubscriptionStatus::None && sub.status != status {
+                continue;
+            }
+            if let Some(token) = &token {
+                if &sub.token_identifier != token {
+                    continue;
+                }
+            }
+            out.push(sub);
+        }
+        out
+    }
+
+    fn usage_attestation_message(&self, sub_id: u64, cycle_block: u64, usage: u64) -> ManagedBuffer {
+        let mut message = self.blockchain().get_sc_address().as_managed_buffer().clone();
+        message.append_bytes(&sub_id.to_be_bytes());
+        message.append_bytes(&cycle_block.to_be_bytes());
+        message.append_bytes(&usage.to_be_bytes());
+        message
     }
 
     // --- VIEWS ---
 
-    #[view(getService)]
     #[storage_mapper("services")]
     fn services(&self, id: u64) -> SingleValueMapper<Service<Self::Api>>;
 
-    #[view(getSubscription)]
+    #[view(getService)]
+    fn get_service(&self, service_id: u64) -> Service<Self::Api> {
+        self.effective_service(service_id)
+    }
+
     #[storage_mapper("subscriptions")]
     fn subscriptions(&self, id: u64) -> SingleValueMapper<Subscription<Self::Api>>;
 
+    // Same keys as `services` and `subscriptions`, read with the schema
+    // version 1 layout during a migration.
+
+    #[storage_mapper("services")]
+    fn legacy_services(&self, id: u64) -> SingleValueMapper<LegacyService<Self::Api>>;
+
+    #[storage_mapper("subscriptions")]
+    fn legacy_subscriptions(&self, id: u64) -> SingleValueMapper<LegacySubscription<Self::Api>>;
+
+    #[view(getSchemaVersion)]
+    #[storage_mapper("schemaVersion")]
+    fn schema_version(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("schemaMigration")]
+    fn schema_migration(&self) -> SingleValueMapper<SchemaMigration>;
+
     #[view(getAllServiceIds)]
     fn get_all_service_ids(&self) -> ManagedVec<u64> {
         let mut out = ManagedVec::new();
@@ -382,19 +2704,418 @@ pub trait SubscriptionContract {
         out
     }
 
+    // Paginated views select the window `offset..offset + limit` of the
+    // underlying id list (capped at MAX_PAGE_SIZE); filters are applied inside
+    // that window, so a page may hold fewer than `limit` records.
+    // A status filter of `None` matches any status.
+
+    #[view(getServicesPage)]
+    fn get_services_page(
+        &self,
+        offset: usize,
+        limit: usize,
+        active_only: bool,
+        opt_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> MultiValueEncoded<Service<Self::Api>> {
+        self.services_page(&self.service_ids(), offset, limit, active_only, opt_token.into_option())
+    }
+
+    #[view(getProviderServicesPage)]
+    fn get_provider_services_page(
+        &self,
+        address: ManagedAddress,
+        offset: usize,
+        limit: usize,
+        active_only: bool,
+        opt_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> MultiValueEncoded<Service<Self::Api>> {
+        self.services_page(
+            &self.provider_services(&address),
+            offset,
+            limit,
+            active_only,
+            opt_token.into_option(),
+        )
+    }
+
+    #[view(getUserSubscriptionsPage)]
+    fn get_user_subscriptions_page(
+        &self,
+        address: ManagedAddress,
+        offset: usize,
+        limit: usize,
+        status: SubscriptionStatus,
+        opt_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> MultiValueEncoded<Subscription<Self::Api>> {
+        self.subscriptions_page(
+            &self.user_subscriptions(&address),
+            offset,
+            limit,
+            status,
+            opt_token.into_option(),
+        )
+    }
+
+    #[view(getProviderSubscriptionsPage)]
+    fn get_provider_subscriptions_page(
+        &self,
+        address: ManagedAddress,
+        offset: usize,
+        limit: usize,
+        status: SubscriptionStatus,
+        opt_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> MultiValueEncoded<Subscription<Self::Api>> {
+        self.subscriptions_page(
+            &self.provider_subscriptions(&address),
+            offset,
+            limit,
+            status,
+            opt_token.into_option(),
+        )
+    }
+
+    #[view(getServiceSubscriptionsPage)]
+    fn get_service_subscriptions_page(
+        &self,
+        service_id: u64,
+        offset: usize,
+        limit: usize,
+        status: SubscriptionStatus,
+        opt_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> MultiValueEncoded<Subscription<Self::Api>> {
+        self.subscriptions_page(
+            &self.service_subscriptions(service_id),
+            offset,
+            limit,
+            status,
+            opt_token.into_option(),
+        )
+    }
+
+    #[view(getUserActiveSubscriptions)]
+    fn get_user_active_subscriptions(&self, address: ManagedAddress) -> ManagedVec<u64> {
+        let mut out = ManagedVec::new();
+        for id in self.user_active_subscriptions(&address).iter() {
+            out.push(id);
+        }
+        out
+    }
+
+    #[view(getProviderActiveSubscriptions)]
+    fn get_provider_active_subscriptions(&self, address: ManagedAddress) -> ManagedVec<u64> {
+        let mut out = ManagedVec::new();
+        for id in self.provider_active_subscriptions(&address).iter() {
+            out.push(id);
+        }
+        out
+    }
+
+    #[view(getServiceActiveSubscriptions)]
+    fn get_service_active_subscriptions(&self, service_id: u64) -> ManagedVec<u64> {
+        let mut out = ManagedVec::new();
+        for id in self.service_active_subscriptions(service_id).iter() {
+            out.push(id);
+        }
+        out
+    }
+
+    /// True while the address has paid access to the service, directly or
+    /// through a bundle that includes it.
+    #[view(isSubscribed)]
+    fn is_subscribed(&self, address: ManagedAddress, service_id: u64) -> bool {
+        self.access_until(&address, service_id) > self.blockchain().get_block_nonce()
+    }
+
+    /// Last block (exclusive) covered by any of the address's live
+    /// subscriptions to the service, or zero if it holds none.
+    #[view(hasAccessUntil)]
+    fn has_access_until(&self, address: ManagedAddress, service_id: u64) -> u64 {
+        self.access_until(&address, service_id)
+    }
+
+    /// Works for both live and archived subscriptions.
+    #[view(getSubscriptionSummary)]
+    fn get_subscription_summary(&self, sub_id: u64) -> SubscriptionSummary<Self::Api> {
+        self.subscription_summary(sub_id)
+    }
+
+    #[view(getUserSubscriptionHistory)]
+    fn get_user_subscription_history(
+        &self,
+        address: ManagedAddress,
+        offset: usize,
+        limit: usize,
+    ) -> MultiValueEncoded<SubscriptionSummary<Self::Api>> {
+        let ids = self.user_subscriptions(&address);
+        let mut out = MultiValueEncoded::new();
+        let limit = core::cmp::min(limit, MAX_PAGE_SIZE);
+        let end = core::cmp::min(ids.len(), offset.saturating_add(limit));
+        for index in offset..end {
+            out.push(self.subscription_summary(ids.get(index + 1)));
+        }
+        out
+    }
+
+    #[view(getActiveServicesByCategory)]
+    fn get_active_services_by_category(
+        &self,
+        category_id: u32,
+        offset: usize,
+        limit: usize,
+    ) -> MultiValueEncoded<Service<Self::Api>> {
+        self.indexed_services_page(&self.category_services(category_id), offset, limit)
+    }
+
+    #[view(getActiveServicesByTag)]
+    fn get_active_services_by_tag(
+        &self,
+        tag: ManagedBuffer,
+        offset: usize,
+        limit: usize,
+    ) -> MultiValueEncoded<Service<Self::Api>> {
+        self.indexed_services_page(&self.tag_services(&tag), offset, limit)
+    }
+
+    #[view(getActiveServicesByToken)]
+    fn get_active_services_by_token(
+        &self,
+        token: EgldOrEsdtTokenIdentifier,
+        offset: usize,
+        limit: usize,
+    ) -> MultiValueEncoded<Service<Self::Api>> {
+        self.indexed_services_page(&self.token_services(&token), offset, limit)
+    }
+
+    #[view(getCategoryServiceCount)]
+    fn get_category_service_count(&self, category_id: u32) -> usize {
+        self.category_services(category_id).len()
+    }
+
+    #[view(getTagServiceCount)]
+    fn get_tag_service_count(&self, tag: ManagedBuffer) -> usize {
+        self.tag_services(&tag).len()
+    }
+
+    #[view(getTokenServiceCount)]
+    fn get_token_service_count(&self, token: EgldOrEsdtTokenIdentifier) -> usize {
+        self.token_services(&token).len()
+    }
+
+    #[view(getServiceCount)]
+    fn get_service_count(&self) -> usize {
+        self.service_ids().len()
+    }
+
+    #[view(getProviderServiceCount)]
+    fn get_provider_service_count(&self, address: ManagedAddress) -> usize {
+        self.provider_services(&address).len()
+    }
+
+    #[view(getUserSubscriptionCount)]
+    fn get_user_subscription_count(&self, address: ManagedAddress) -> usize {
+        self.user_subscriptions(&address).len()
+    }
+
+    #[view(getProviderSubscriptionCount)]
+    fn get_provider_subscription_count(&self, address: ManagedAddress) -> usize {
+        self.provider_subscriptions(&address).len()
+    }
+
+    #[view(getServiceSubscriptionCount)]
+    fn get_service_subscription_count(&self, service_id: u64) -> usize {
+        self.service_subscriptions(service_id).len()
+    }
+
+    /// Archived subscriptions keep their final status; their balances and
+    /// schedule read as zero.
+    #[view(getSubscription)]
+    fn get_subscription(&self, sub_id: u64) -> Subscription<Self::Api> {
+        self.live_or_archived_subscription(sub_id)
+    }
+
     #[view(getSubscriptionPaymentInfo)]
     fn get_subscription_payment_info(
         &self,
         sub_id: u64,
-    ) -> MultiValue4<u8, u64, BigUint, BigUint> {
-        let sub = self.subscriptions(sub_id).get();
-        MultiValue4::from((sub.status, sub.next_payment_block, sub.remaining_balance, sub.amount_per_cycle))
+    ) -> MultiValue4<SubscriptionStatus, u64, BigUint, BigUint> {
+        let sub = self.live_or_archived_subscription(sub_id);
+        let cycle_charge = sub.cycle_charge();
+        MultiValue4::from((sub.status, sub.next_payment_block, sub.remaining_balance, cycle_charge))
     }
 
     #[view(getSubscriptionState)]
-    fn get_subscription_state(&self, sub_id: u64) -> MultiValue3<u8, u64, u64> {
-        let sub = self.subscriptions(sub_id).get();
-        MultiValue3::from((sub.status, sub.next_payment_block, sub.cancel_effective_block))
+    fn get_subscription_state(
+        &self,
+        sub_id: u64,
+    ) -> MultiValue4<SubscriptionStatus, u64, u64, u64> {
+        let sub = self.live_or_archived_subscription(sub_id);
+        MultiValue4::from((
+            sub.status,
+            sub.next_payment_block,
+            sub.cancel_effective_block,
+            sub.commitment_end_block,
+        ))
+    }
+
+    #[view(getPendingUsage)]
+    fn get_pending_usage(&self, sub_id: u64) -> OptionalValue<UsageReport> {
+        if self.pending_usage(sub_id).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.pending_usage(sub_id).get())
+    }
+
+    #[view(getServiceDeactivation)]
+    fn get_service_deactivation(&self, service_id: u64) -> OptionalValue<ServiceDeactivation> {
+        if self.service_deactivation(service_id).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.service_deactivation(service_id).get())
+    }
+
+    /// The scheduled price change that has not taken effect yet, if any.
+    #[view(getPendingPriceChange)]
+    fn get_pending_price_change(&self, service_id: u64) -> OptionalValue<PriceChange<Self::Api>> {
+        let count = self.price_change_count(service_id).get();
+        if count == 0 {
+            return OptionalValue::None;
+        }
+        let change = self.price_changes(service_id, count).get();
+        if change.effective_block <= self.blockchain().get_block_nonce() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(change)
+    }
+
+    #[view(isServiceTransferable)]
+    fn is_service_transferable(&self, service_id: u64) -> bool {
+        !self.service_non_transferable(service_id).get()
+    }
+
+    /// A non-zero `deficit` means the accounting invariant is broken.
+    #[view(getReconciliation)]
+    fn get_reconciliation(
+        &self,
+        token: EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+    ) -> Reconciliation<Self::Api> {
+        let liabilities = self.total_liabilities(&token, nonce).get();
+        let balance = self.blockchain().get_sc_balance(&token, nonce);
+        let (surplus, deficit) = if balance >= liabilities {
+            (&balance - &liabilities, BigUint::zero())
+        } else {
+            (BigUint::zero(), &liabilities - &balance)
+        };
+        Reconciliation {
+            liabilities,
+            balance,
+            surplus,
+            deficit,
+        }
+    }
+
+    #[view(getSchemaMigration)]
+    fn get_schema_migration(&self) -> OptionalValue<SchemaMigration> {
+        if self.schema_migration().is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.schema_migration().get())
+    }
+
+    #[view(getGovernanceProposal)]
+    fn get_governance_proposal(
+        &self,
+        proposal_id: u64,
+    ) -> OptionalValue<GovernanceProposal<Self::Api>> {
+        if self.governance_proposals(proposal_id).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.governance_proposals(proposal_id).get())
+    }
+
+    #[view(getPendingGovernanceProposals)]
+    fn get_pending_governance_proposals(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<u64, GovernanceProposal<Self::Api>>> {
+        let mut result = MultiValueEncoded::new();
+        for proposal_id in self.pending_governance_proposals().iter() {
+            let proposal = self.governance_proposals(proposal_id).get();
+            result.push(MultiValue2::from((proposal_id, proposal)));
+        }
+        result
+    }
+
+    #[view(getPendingTransfer)]
+    fn get_pending_transfer(&self, sub_id: u64) -> OptionalValue<ManagedAddress> {
+        if self.pending_transfer(sub_id).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.pending_transfer(sub_id).get())
+    }
+
+    #[view(getDispute)]
+    fn get_dispute(&self, sub_id: u64) -> OptionalValue<Dispute<Self::Api>> {
+        if self.disputes(sub_id).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.disputes(sub_id).get())
+    }
+
+    /// Retained settlements, oldest first. The lifetime total charged is
+    /// the subscription's `total_spent`.
+    #[view(getPaymentHistory)]
+    fn get_payment_history(
+        &self,
+        sub_id: u64,
+        offset: u64,
+        limit: u64,
+    ) -> MultiValueEncoded<Settlement<Self::Api>> {
+        let mut out = MultiValueEncoded::new();
+        let count = self.settlement_count(sub_id).get();
+        let first = count.saturating_sub(PAYMENT_HISTORY_SIZE);
+        let start = first.saturating_add(offset);
+        let end = core::cmp::min(count, start.saturating_add(limit));
+        for index in start..end {
+            out.push(self.settlements(sub_id, index % PAYMENT_HISTORY_SIZE).get());
+        }
+        out
+    }
+
+    #[view(getInvoice)]
+    fn get_invoice(&self, provider: ManagedAddress, number: u64) -> Invoice<Self::Api> {
+        require!(!self.invoices(&provider, number).is_empty(), "Invoice not found");
+        self.invoices(&provider, number).get()
+    }
+
+    #[view(getServiceStats)]
+    fn get_service_stats(&self, service_id: u64) -> SubscriberStats {
+        if self.service_stats(service_id).is_empty() {
+            return SubscriberStats::default();
+        }
+        self.service_stats(service_id).get()
+    }
+
+    #[view(getProviderStats)]
+    fn get_provider_stats(&self, address: ManagedAddress) -> SubscriberStats {
+        if self.provider_stats(&address).is_empty() {
+            return SubscriberStats::default();
+        }
+        self.provider_stats(&address).get()
+    }
+
+    #[view(canSubscribe)]
+    fn can_subscribe(&self, service_id: u64, address: ManagedAddress) -> bool {
+        self.has_service_access(service_id, &address) && self.bundle_open_to(service_id, &address)
+    }
+
+    #[view(getServiceAllowlist)]
+    fn get_service_allowlist(&self, service_id: u64) -> MultiValueEncoded<ManagedAddress> {
+        self.service_allowlist(service_id).iter().collect()
+    }
+
+    #[view(getServiceBlocklist)]
+    fn get_service_blocklist(&self, service_id: u64) -> MultiValueEncoded<ManagedAddress> {
+        self.service_blocklist(service_id).iter().collect()
     }
 
     #[view(getUserRole)]
@@ -413,9 +3134,175 @@ pub trait SubscriptionContract {
     #[storage_mapper("lastSubscriptionId")]
     fn last_subscription_id(&self) -> SingleValueMapper<u64>;
 
+    #[view(getUsageDisputeWindow)]
+    #[storage_mapper("usageDisputeWindow")]
+    fn usage_dispute_window(&self) -> SingleValueMapper<u64>;
+
+    #[view(getServiceMetering)]
+    #[storage_mapper("serviceMetering")]
+    fn service_metering(&self, service_id: u64) -> SingleValueMapper<MeteringConfig<Self::Api>>;
+
+    #[view(getSubscriptionMetering)]
+    #[storage_mapper("subscriptionMetering")]
+    fn subscription_metering(&self, sub_id: u64) -> SingleValueMapper<MeteringConfig<Self::Api>>;
+
+    #[view(getServiceCommitment)]
+    #[storage_mapper("serviceCommitment")]
+    fn service_commitment(&self, service_id: u64) -> SingleValueMapper<CommitmentTerms<Self::Api>>;
+
+    #[view(getServiceMetadata)]
+    #[storage_mapper("serviceMetadata")]
+    fn service_metadata(&self, service_id: u64) -> SingleValueMapper<ServiceMetadata<Self::Api>>;
+
+    #[storage_mapper("categoryServices")]
+    fn category_services(&self, category_id: u32) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("tagServices")]
+    fn tag_services(&self, tag: &ManagedBuffer) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("tokenServices")]
+    fn token_services(&self, token: &EgldOrEsdtTokenIdentifier) -> UnorderedSetMapper<u64>;
+
+    #[view(getTotalLiabilities)]
+    #[storage_mapper("totalLiabilities")]
+    fn total_liabilities(
+        &self,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getServiceBondAmount)]
+    #[storage_mapper("serviceBondAmount")]
+    fn service_bond_amount(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getBondCooldown)]
+    #[storage_mapper("bondCooldown")]
+    fn bond_cooldown(&self) -> SingleValueMapper<u64>;
+
+    #[view(getServiceBond)]
+    #[storage_mapper("serviceBond")]
+    fn service_bond(&self, service_id: u64) -> SingleValueMapper<BigUint>;
+
+    #[view(getServiceDeactivatedBlock)]
+    #[storage_mapper("serviceDeactivatedBlock")]
+    fn service_deactivated_block(&self, service_id: u64) -> SingleValueMapper<u64>;
+
+    #[view(isServiceDelisted)]
+    #[storage_mapper("serviceDelisted")]
+    fn service_delisted(&self, service_id: u64) -> SingleValueMapper<bool>;
+
+    #[view(getModerators)]
+    #[storage_mapper("moderators")]
+    fn moderators(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("serviceDeactivation")]
+    fn service_deactivation(&self, service_id: u64) -> SingleValueMapper<ServiceDeactivation>;
+
+
+    #[view(getGovernanceDelay)]
+    #[storage_mapper("governanceDelay")]
+    fn governance_delay(&self) -> SingleValueMapper<u64>;
+
+    #[view(getLastGovernanceProposalId)]
+    #[storage_mapper("lastGovernanceProposalId")]
+    fn last_governance_proposal_id(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("governanceProposals")]
+    fn governance_proposals(&self, proposal_id: u64) -> SingleValueMapper<GovernanceProposal<Self::Api>>;
+
+    #[storage_mapper("pendingGovernanceProposals")]
+    fn pending_governance_proposals(&self) -> UnorderedSetMapper<u64>;
+
+    #[view(getMinPriceNotice)]
+    #[storage_mapper("minPriceNotice")]
+    fn min_price_notice(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("priceChangeCount")]
+    fn price_change_count(&self, service_id: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("priceChanges")]
+    fn price_changes(&self, service_id: u64, epoch: u64) -> SingleValueMapper<PriceChange<Self::Api>>;
+
+    /// Number of price changes already folded into the stored service.
+    #[storage_mapper("servicePriceEpoch")]
+    fn service_price_epoch(&self, service_id: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("serviceNonTransferable")]
+    fn service_non_transferable(&self, service_id: u64) -> SingleValueMapper<bool>;
+
+    #[view(getSubscriptionMembers)]
+    #[storage_mapper("subscriptionMembers")]
+    fn subscription_members(&self, sub_id: u64) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getMemberSubscriptions)]
+    #[storage_mapper("memberSubscriptions")]
+    fn member_subscriptions(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("pendingTransfer")]
+    fn pending_transfer(&self, sub_id: u64) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getBundleServices)]
+    #[storage_mapper("bundleServices")]
+    fn bundle_services(&self, bundle_id: u64) -> UnorderedSetMapper<u64>;
+
+    #[view(getServiceBundles)]
+    #[storage_mapper("serviceBundles")]
+    fn service_bundles(&self, service_id: u64) -> UnorderedSetMapper<u64>;
+
+    #[view(getServiceHook)]
+    #[storage_mapper("serviceHook")]
+    fn service_hook(&self, service_id: u64) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getHookFailures)]
+    #[storage_mapper("hookFailures")]
+    fn hook_failures(&self, service_id: u64) -> SingleValueMapper<u64>;
+
+    #[view(getServiceAccessMode)]
+    #[storage_mapper("serviceAccessMode")]
+    fn service_access_mode(&self, service_id: u64) -> SingleValueMapper<AccessMode>;
+
+    #[storage_mapper("serviceAllowlist")]
+    fn service_allowlist(&self, service_id: u64) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("serviceBlocklist")]
+    fn service_blocklist(&self, service_id: u64) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getArbiter)]
+    #[storage_mapper("arbiter")]
+    fn arbiter(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getServiceArbiter)]
+    #[storage_mapper("serviceArbiter")]
+    fn service_arbiter(&self, service_id: u64) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getDisputeTimeout)]
+    #[storage_mapper("disputeTimeout")]
+    fn dispute_timeout(&self) -> SingleValueMapper<u64>;
+
+    #[view(getDisputeDefaultProviderShare)]
+    #[storage_mapper("disputeDefaultProviderShare")]
+    fn dispute_default_provider_share(&self) -> SingleValueMapper<u64>;
+
+    #[view(getHeldPayment)]
+    #[storage_mapper("heldPayment")]
+    fn held_payment(&self, sub_id: u64) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("disputes")]
+    fn disputes(&self, sub_id: u64) -> SingleValueMapper<Dispute<Self::Api>>;
+
+    #[storage_mapper("pendingUsage")]
+    fn pending_usage(&self, sub_id: u64) -> SingleValueMapper<UsageReport>;
+
+    #[storage_mapper("lastUsageReportCycle")]
+    fn last_usage_report_cycle(&self, sub_id: u64) -> SingleValueMapper<u64>;
+
     #[storage_mapper("serviceIds")]
     fn service_ids(&self) -> VecMapper<u64>;
 
+    /// 1-based position of the service in `serviceIds`.
+    #[storage_mapper("serviceListingIndex")]
+    fn service_listing_index(&self, service_id: u64) -> SingleValueMapper<usize>;
+
     #[storage_mapper("providerServices")]
     fn provider_services(&self, address: &ManagedAddress) -> VecMapper<u64>;
 
@@ -428,6 +3315,69 @@ pub trait SubscriptionContract {
     #[storage_mapper("serviceSubscriptions")]
     fn service_subscriptions(&self, service_id: u64) -> VecMapper<u64>;
 
+    #[storage_mapper("userActiveSubscriptions")]
+    fn user_active_subscriptions(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("providerActiveSubscriptions")]
+    fn provider_active_subscriptions(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("serviceActiveSubscriptions")]
+    fn service_active_subscriptions(&self, service_id: u64) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("archivedSubscriptions")]
+    fn archived_subscriptions(&self, sub_id: u64) -> SingleValueMapper<SubscriptionSummary<Self::Api>>;
+
+    #[view(isRevivalApproved)]
+    #[storage_mapper("revivalApproved")]
+    fn revival_approved(&self, sub_id: u64) -> SingleValueMapper<bool>;
+
+    /// Number of settlements ever recorded for the subscription.
+    #[view(getSettlementCount)]
+    #[storage_mapper("settlementCount")]
+    fn settlement_count(&self, sub_id: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("settlements")]
+    fn settlements(&self, sub_id: u64, slot: u64) -> SingleValueMapper<Settlement<Self::Api>>;
+
+    #[view(getLastInvoiceNumber)]
+    #[storage_mapper("lastInvoiceNumber")]
+    fn last_invoice_number(&self, provider: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("invoices")]
+    fn invoices(&self, provider: &ManagedAddress, number: u64) -> SingleValueMapper<Invoice<Self::Api>>;
+
+    #[storage_mapper("serviceStats")]
+    fn service_stats(&self, service_id: u64) -> SingleValueMapper<SubscriberStats>;
+
+    #[storage_mapper("providerStats")]
+    fn provider_stats(&self, address: &ManagedAddress) -> SingleValueMapper<SubscriberStats>;
+
+    #[view(getServiceTotalCollected)]
+    #[storage_mapper("serviceTotalCollected")]
+    fn service_total_collected(&self, service_id: u64) -> SingleValueMapper<BigUint>;
+
+    #[view(getProviderTotalCollected)]
+    #[storage_mapper("providerTotalCollected")]
+    fn provider_total_collected(
+        &self,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    /// Subscriber funds held by the contract: unspent balances, escrowed
+    /// termination fees and dispute escrows.
+    #[view(getServiceCommittedEscrow)]
+    #[storage_mapper("serviceCommittedEscrow")]
+    fn service_committed_escrow(&self, service_id: u64) -> SingleValueMapper<BigUint>;
+
+    #[view(getProviderCommittedEscrow)]
+    #[storage_mapper("providerCommittedEscrow")]
+    fn provider_committed_escrow(
+        &self,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
     #[storage_mapper("userRole")]
     fn user_role(&self, address: &ManagedAddress) -> SingleValueMapper<u8>;
 
@@ -0,0 +1,2345 @@
+// Code generated by the multiversx-sc proxy generator. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+#![allow(dead_code)]
+#![allow(clippy::all)]
+
+use multiversx_sc::proxy_imports::*;
+
+pub struct SubscriptionContractProxy;
+
+impl<Env, From, To, Gas> TxProxyTrait<Env, From, To, Gas> for SubscriptionContractProxy
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    type TxProxyMethods = SubscriptionContractProxyMethods<Env, From, To, Gas>;
+
+    fn proxy_methods(self, tx: Tx<Env, From, To, (), Gas, (), ()>) -> Self::TxProxyMethods {
+        SubscriptionContractProxyMethods { wrapped_tx: tx }
+    }
+}
+
+pub struct SubscriptionContractProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    wrapped_tx: Tx<Env, From, To, (), Gas, (), ()>,
+}
+
+#[rustfmt::skip]
+impl<Env, From, Gas> SubscriptionContractProxyMethods<Env, From, (), Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    Gas: TxGas<Env>,
+{
+    /// The governance delay defaults to `DEFAULT_GOVERNANCE_DELAY_BLOCKS`. 
+    pub fn init<
+        Arg0: ProxyArg<OptionalValue<u64>>,
+    >(
+        self,
+        opt_governance_delay: Arg0,
+    ) -> TxTypedDeploy<Env, From, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_deploy()
+            .argument(&opt_governance_delay)
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> SubscriptionContractProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    /// Settings introduced since the deployed version get their defaults. 
+    /// Records in an older layout stay unreadable until `migrateRecords` 
+    /// has been called enough times to complete the migration; until then, 
+    /// subscribing, top-ups and scheduled payments fail with 
+    /// "Migration in progress". 
+    pub fn upgrade(
+        self,
+    ) -> TxTypedUpgrade<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_upgrade()
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> SubscriptionContractProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    /// Re-encodes up to `batch_size` legacy records, services first, and 
+    /// rebuilds the indexes and statistics derived from them. Returns true 
+    /// once every record is in the current layout. 
+    pub fn migrate_records<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        batch_size: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("migrateRecords")
+            .argument(&batch_size)
+            .original_result()
+    }
+
+    /// Settings are never changed directly: the owner queues an action, 
+    /// which can be executed once `governanceDelay` blocks have passed. 
+    pub fn propose_governance_action<
+        Arg0: ProxyArg<GovernanceAction<Env::Api>>,
+    >(
+        self,
+        action: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("proposeGovernanceAction")
+            .argument(&action)
+            .original_result()
+    }
+
+    pub fn execute_governance_action<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        proposal_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("executeGovernanceAction")
+            .argument(&proposal_id)
+            .original_result()
+    }
+
+    pub fn cancel_governance_action<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        proposal_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelGovernanceAction")
+            .argument(&proposal_id)
+            .original_result()
+    }
+
+    pub fn register_as_user(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("registerAsUser")
+            .original_result()
+    }
+
+    pub fn register_as_provider(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("registerAsProvider")
+            .original_result()
+    }
+
+    /// A non-zero token nonce makes the service bill in that specific 
+    /// SFT or Meta-ESDT (e.g. an LP token position). The EGLD service bond 
+    /// must be paid in; it is returned when the service is retired. 
+    pub fn create_service<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+        Arg3: ProxyArg<u64>,
+        Arg4: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+        Arg5: ProxyArg<OptionalValue<u64>>,
+    >(
+        self,
+        name: Arg0,
+        description: Arg1,
+        amount_per_cycle: Arg2,
+        frequency_in_blocks: Arg3,
+        opt_token_id: Arg4,
+        opt_token_nonce: Arg5,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u64> {
+        self.wrapped_tx
+            .raw_call("createService")
+            .argument(&name)
+            .argument(&description)
+            .argument(&amount_per_cycle)
+            .argument(&frequency_in_blocks)
+            .argument(&opt_token_id)
+            .argument(&opt_token_nonce)
+            .original_result()
+    }
+
+    /// A bundle is a service of its own, with its own price and billing, 
+    /// whose subscriptions also give access to each of the member services. 
+    /// Members must be active, non-bundle services of the caller that bill 
+    /// in the same token. Bundles take a service bond as well. 
+    pub fn create_bundle<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+        Arg3: ProxyArg<u64>,
+        Arg4: ProxyArg<MultiValueEncoded<Env::Api, u64>>,
+    >(
+        self,
+        name: Arg0,
+        description: Arg1,
+        amount_per_cycle: Arg2,
+        frequency_in_blocks: Arg3,
+        service_ids: Arg4,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u64> {
+        self.wrapped_tx
+            .raw_call("createBundle")
+            .argument(&name)
+            .argument(&description)
+            .argument(&amount_per_cycle)
+            .argument(&frequency_in_blocks)
+            .argument(&service_ids)
+            .original_result()
+    }
+
+    /// Stops new signups and, depending on the mode, winds down existing 
+    /// subscriptions in batches; call `continueServiceDeactivation` until 
+    /// the deactivation is completed. `TerminateNow` refunds the unused part 
+    /// of the current cycle out of the payment still held for it. 
+    pub fn deactivate_service<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<DeactivationMode>>,
+    >(
+        self,
+        service_id: Arg0,
+        opt_mode: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("deactivateService")
+            .argument(&service_id)
+            .argument(&opt_mode)
+            .original_result()
+    }
+
+    pub fn continue_service_deactivation<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("continueServiceDeactivation")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn reactivate_service<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("reactivateService")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    /// Delists a deactivated service once it has no subscriptions left 
+    /// running, and returns the service bond to the provider. The bond 
+    /// stays slashable for `getBondCooldown` blocks after the service was 
+    /// deactivated. 
+    pub fn retire_service<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("retireService")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    /// Schedules new billing terms for the service. A change that has not 
+    /// taken effect yet is replaced; the notice period applies again. 
+    pub fn update_service_price<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+        amount_per_cycle: Arg1,
+        frequency_in_blocks: Arg2,
+        effective_block: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("updateServicePrice")
+            .argument(&service_id)
+            .argument(&amount_per_cycle)
+            .argument(&frequency_in_blocks)
+            .argument(&effective_block)
+            .original_result()
+    }
+
+    /// `max_subscribers` of zero means no cap. Replaces any previous 
+    /// metadata, including the category and tag listings. 
+    pub fn set_service_metadata<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg3: ProxyArg<ManagedByteArray<Env::Api, 32usize>>,
+        Arg4: ProxyArg<u64>,
+        Arg5: ProxyArg<MultiValueEncoded<Env::Api, ManagedBuffer<Env::Api>>>,
+    >(
+        self,
+        service_id: Arg0,
+        category_id: Arg1,
+        details_uri: Arg2,
+        content_hash: Arg3,
+        max_subscribers: Arg4,
+        tags: Arg5,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setServiceMetadata")
+            .argument(&service_id)
+            .argument(&category_id)
+            .argument(&details_uri)
+            .argument(&content_hash)
+            .argument(&max_subscribers)
+            .argument(&tags)
+            .original_result()
+    }
+
+    /// Metering applies to subscriptions created after this call; 
+    /// a zero unit price turns metering off. 
+    pub fn set_service_metering<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+        unit_price: Arg1,
+        usage_cap_per_cycle: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setServiceMetering")
+            .argument(&service_id)
+            .argument(&unit_price)
+            .argument(&usage_cap_per_cycle)
+            .original_result()
+    }
+
+    /// Commitment terms apply to subscriptions created after this call; 
+    /// zero minimum cycles removes the commitment. 
+    pub fn set_service_commitment<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        service_id: Arg0,
+        min_cycles: Arg1,
+        early_termination_fee: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setServiceCommitment")
+            .argument(&service_id)
+            .argument(&min_cycles)
+            .argument(&early_termination_fee)
+            .original_result()
+    }
+
+    /// Registers a contract notified on subscription lifecycle changes: 
+    /// `onSubscriptionStarted(sub_id, client, paid_until_block)`, 
+    /// `onSubscriptionRenewed(sub_id, client, paid_until_block)`, 
+    /// `onSubscriptionEnded(sub_id, client, status)` and 
+    /// `onSubscriptionTransferred(sub_id, new_client, previous_client)`. 
+    pub fn set_service_hook<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        service_id: Arg0,
+        opt_hook: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setServiceHook")
+            .argument(&service_id)
+            .argument(&opt_hook)
+            .original_result()
+    }
+
+    /// Subscriptions are transferable unless the provider turns it off. 
+    pub fn set_service_transferable<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        service_id: Arg0,
+        transferable: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setServiceTransferable")
+            .argument(&service_id)
+            .argument(&transferable)
+            .original_result()
+    }
+
+    pub fn set_service_access_mode<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<AccessMode>,
+    >(
+        self,
+        service_id: Arg0,
+        mode: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setServiceAccessMode")
+            .argument(&service_id)
+            .argument(&mode)
+            .original_result()
+    }
+
+    pub fn add_to_service_allowlist<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        service_id: Arg0,
+        addresses: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addToServiceAllowlist")
+            .argument(&service_id)
+            .argument(&addresses)
+            .original_result()
+    }
+
+    pub fn remove_from_service_allowlist<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        service_id: Arg0,
+        addresses: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeFromServiceAllowlist")
+            .argument(&service_id)
+            .argument(&addresses)
+            .original_result()
+    }
+
+    /// With `cancel_existing`, active subscriptions the listed addresses 
+    /// hold for this service are moved to provider-pending cancellation. 
+    pub fn add_to_service_blocklist<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<bool>,
+        Arg2: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        service_id: Arg0,
+        cancel_existing: Arg1,
+        addresses: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addToServiceBlocklist")
+            .argument(&service_id)
+            .argument(&cancel_existing)
+            .argument(&addresses)
+            .original_result()
+    }
+
+    pub fn remove_from_service_blocklist<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        service_id: Arg0,
+        addresses: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeFromServiceBlocklist")
+            .argument(&service_id)
+            .argument(&addresses)
+            .original_result()
+    }
+
+    /// Stops new signups and removes the service from `serviceIds`. 
+    /// Existing subscriptions keep running until they end. 
+    pub fn delist_service<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("delistService")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    /// Sends the service bond to the contract owner. 
+    pub fn slash_service_bond<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("slashServiceBond")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    /// The optional limits default to zero, meaning unlimited. 
+    pub fn subscribe<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<BigUint<Env::Api>>>,
+        Arg2: ProxyArg<OptionalValue<u64>>,
+        Arg3: ProxyArg<OptionalValue<u64>>,
+    >(
+        self,
+        service_id: Arg0,
+        opt_max_total_spend: Arg1,
+        opt_max_cycles: Arg2,
+        opt_end_block: Arg3,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u64> {
+        self.wrapped_tx
+            .raw_call("subscribe")
+            .argument(&service_id)
+            .argument(&opt_max_total_spend)
+            .argument(&opt_max_cycles)
+            .argument(&opt_end_block)
+            .original_result()
+    }
+
+    pub fn top_up<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("topUp")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    /// Tops up several subscriptions in one multi-transfer; the n-th 
+    /// payment goes to the n-th subscription id. 
+    pub fn top_up_many<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, u64>>,
+    >(
+        self,
+        sub_ids: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("topUpMany")
+            .argument(&sub_ids)
+            .original_result()
+    }
+
+    /// Lets the provider allow (or stop allowing) a cancelled subscription 
+    /// to be revived by its subscriber. 
+    pub fn set_revival_approval<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        sub_id: Arg0,
+        approved: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRevivalApproval")
+            .argument(&sub_id)
+            .argument(&approved)
+            .original_result()
+    }
+
+    /// Restarts a cancelled subscription the provider approved for revival, 
+    /// charging a new first cycle from the deposit. 
+    pub fn revive_subscription<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("reviveSubscription")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn cancel_subscription_by_user<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelSubscriptionByUser")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn cancel_subscription_by_provider<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelSubscriptionByProvider")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    /// Withdraws a pending cancellation before it takes effect. Only the 
+    /// party that requested it can revoke it; the billing schedule is kept. 
+    pub fn revoke_cancellation<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeCancellation")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    /// First step of handing an active subscription, with its remaining 
+    /// balance, to another user. Replaces any earlier proposal. 
+    pub fn propose_subscription_transfer<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        sub_id: Arg0,
+        recipient: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("proposeSubscriptionTransfer")
+            .argument(&sub_id)
+            .argument(&recipient)
+            .original_result()
+    }
+
+    pub fn cancel_subscription_transfer<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelSubscriptionTransfer")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    /// The subscription stays in the previous owner's history; only their 
+    /// active set drops it. Team members added by the previous owner lose 
+    /// their access. 
+    pub fn accept_subscription_transfer<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("acceptSubscriptionTransfer")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    /// Extra seats are charged right away from the balance, prorated to 
+    /// the rest of the current cycle. Fewer seats take effect from the 
+    /// next cycle, without a refund. 
+    pub fn set_seats<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+        seats: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setSeats")
+            .argument(&sub_id)
+            .argument(&seats)
+            .original_result()
+    }
+
+    pub fn add_subscription_members<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        sub_id: Arg0,
+        members: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addSubscriptionMembers")
+            .argument(&sub_id)
+            .argument(&members)
+            .original_result()
+    }
+
+    pub fn remove_subscription_members<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        sub_id: Arg0,
+        members: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeSubscriptionMembers")
+            .argument(&sub_id)
+            .argument(&members)
+            .original_result()
+    }
+
+    /// An attestation is the subscriber's ed25519 signature over 
+    /// `sc_address | sub_id | cycle_block | usage`, the numbers encoded 
+    /// as 8-byte big-endian, where `cycle_block` is the subscription's 
+    /// `next_payment_block` at the time of the report. 
+    /// Attested reports skip the dispute window. 
+    pub fn report_usage<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<OptionalValue<ManagedBuffer<Env::Api>>>,
+    >(
+        self,
+        sub_id: Arg0,
+        usage: Arg1,
+        opt_attestation: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("reportUsage")
+            .argument(&sub_id)
+            .argument(&usage)
+            .argument(&opt_attestation)
+            .original_result()
+    }
+
+    /// Discards an unattested usage report while it is still inside the 
+    /// dispute window. The provider may resubmit it with an attestation. 
+    pub fn dispute_usage<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("disputeUsage")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn open_dispute<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("openDispute")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    /// Pays `provider_share_bps` of the escrow to the provider, refunds 
+    /// the rest to the subscriber and unfreezes the subscription. 
+    pub fn resolve_dispute<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+        provider_share_bps: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("resolveDispute")
+            .argument(&sub_id)
+            .argument(&provider_share_bps)
+            .original_result()
+    }
+
+    /// Settles a dispute the arbiter did not resolve in time, 
+    /// using the default provider share. 
+    pub fn expire_dispute<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("expireDispute")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    /// Each cycle payment is held until the next one is charged, and only 
+    /// then released to the provider. Cycle payments of a disputed 
+    /// subscription are kept in the dispute escrow until it is resolved. 
+    pub fn trigger_payment<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("triggerPayment")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn finalize_cancellation<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("finalizeCancellation")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    /// Replaces a terminated subscription with a compact summary and clears 
+    /// its per-subscription storage, keeping the bounded payment history. 
+    /// MultiversX does not refund gas for freed storage, but the state no 
+    /// longer has to be carried around. A subscription the provider approved 
+    /// for revival is kept until the approval is withdrawn. 
+    pub fn archive_subscription<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("archiveSubscription")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn get_service<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Service<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getService")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn schema_version(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSchemaVersion")
+            .original_result()
+    }
+
+    pub fn get_all_service_ids(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedVec<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAllServiceIds")
+            .original_result()
+    }
+
+    pub fn get_provider_services<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedVec<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProviderServices")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_user_subscriptions<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedVec<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUserSubscriptions")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_provider_subscriptions<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedVec<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProviderSubscriptions")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_service_subscriptions<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedVec<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceSubscriptions")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn get_services_page<
+        Arg0: ProxyArg<usize>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<bool>,
+        Arg3: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        offset: Arg0,
+        limit: Arg1,
+        active_only: Arg2,
+        opt_token: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Service<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServicesPage")
+            .argument(&offset)
+            .argument(&limit)
+            .argument(&active_only)
+            .argument(&opt_token)
+            .original_result()
+    }
+
+    pub fn get_provider_services_page<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<usize>,
+        Arg3: ProxyArg<bool>,
+        Arg4: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        address: Arg0,
+        offset: Arg1,
+        limit: Arg2,
+        active_only: Arg3,
+        opt_token: Arg4,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Service<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProviderServicesPage")
+            .argument(&address)
+            .argument(&offset)
+            .argument(&limit)
+            .argument(&active_only)
+            .argument(&opt_token)
+            .original_result()
+    }
+
+    pub fn get_user_subscriptions_page<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<usize>,
+        Arg3: ProxyArg<SubscriptionStatus>,
+        Arg4: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        address: Arg0,
+        offset: Arg1,
+        limit: Arg2,
+        status: Arg3,
+        opt_token: Arg4,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Subscription<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUserSubscriptionsPage")
+            .argument(&address)
+            .argument(&offset)
+            .argument(&limit)
+            .argument(&status)
+            .argument(&opt_token)
+            .original_result()
+    }
+
+    pub fn get_provider_subscriptions_page<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<usize>,
+        Arg3: ProxyArg<SubscriptionStatus>,
+        Arg4: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        address: Arg0,
+        offset: Arg1,
+        limit: Arg2,
+        status: Arg3,
+        opt_token: Arg4,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Subscription<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProviderSubscriptionsPage")
+            .argument(&address)
+            .argument(&offset)
+            .argument(&limit)
+            .argument(&status)
+            .argument(&opt_token)
+            .original_result()
+    }
+
+    pub fn get_service_subscriptions_page<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<usize>,
+        Arg3: ProxyArg<SubscriptionStatus>,
+        Arg4: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        service_id: Arg0,
+        offset: Arg1,
+        limit: Arg2,
+        status: Arg3,
+        opt_token: Arg4,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Subscription<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceSubscriptionsPage")
+            .argument(&service_id)
+            .argument(&offset)
+            .argument(&limit)
+            .argument(&status)
+            .argument(&opt_token)
+            .original_result()
+    }
+
+    pub fn get_user_active_subscriptions<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedVec<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUserActiveSubscriptions")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_provider_active_subscriptions<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedVec<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProviderActiveSubscriptions")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_service_active_subscriptions<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedVec<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceActiveSubscriptions")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    /// True while the address has paid access to the service, directly or 
+    /// through a bundle that includes it. 
+    pub fn is_subscribed<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        address: Arg0,
+        service_id: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isSubscribed")
+            .argument(&address)
+            .argument(&service_id)
+            .original_result()
+    }
+
+    /// Last block (exclusive) covered by any of the address's live 
+    /// subscriptions to the service, or zero if it holds none. 
+    pub fn has_access_until<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        address: Arg0,
+        service_id: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("hasAccessUntil")
+            .argument(&address)
+            .argument(&service_id)
+            .original_result()
+    }
+
+    /// Works for both live and archived subscriptions. 
+    pub fn get_subscription_summary<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, SubscriptionSummary<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSubscriptionSummary")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn get_user_subscription_history<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<usize>,
+    >(
+        self,
+        address: Arg0,
+        offset: Arg1,
+        limit: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, SubscriptionSummary<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUserSubscriptionHistory")
+            .argument(&address)
+            .argument(&offset)
+            .argument(&limit)
+            .original_result()
+    }
+
+    pub fn get_active_services_by_category<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<usize>,
+    >(
+        self,
+        category_id: Arg0,
+        offset: Arg1,
+        limit: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Service<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getActiveServicesByCategory")
+            .argument(&category_id)
+            .argument(&offset)
+            .argument(&limit)
+            .original_result()
+    }
+
+    pub fn get_active_services_by_tag<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<usize>,
+    >(
+        self,
+        tag: Arg0,
+        offset: Arg1,
+        limit: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Service<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getActiveServicesByTag")
+            .argument(&tag)
+            .argument(&offset)
+            .argument(&limit)
+            .original_result()
+    }
+
+    pub fn get_active_services_by_token<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<usize>,
+    >(
+        self,
+        token: Arg0,
+        offset: Arg1,
+        limit: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Service<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getActiveServicesByToken")
+            .argument(&token)
+            .argument(&offset)
+            .argument(&limit)
+            .original_result()
+    }
+
+    pub fn get_category_service_count<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        category_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCategoryServiceCount")
+            .argument(&category_id)
+            .original_result()
+    }
+
+    pub fn get_tag_service_count<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        tag: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTagServiceCount")
+            .argument(&tag)
+            .original_result()
+    }
+
+    pub fn get_token_service_count<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTokenServiceCount")
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn get_service_count(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceCount")
+            .original_result()
+    }
+
+    pub fn get_provider_service_count<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProviderServiceCount")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_user_subscription_count<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUserSubscriptionCount")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_provider_subscription_count<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProviderSubscriptionCount")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_service_subscription_count<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceSubscriptionCount")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    /// Archived subscriptions keep their final status; their balances and 
+    /// schedule read as zero. 
+    pub fn get_subscription<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Subscription<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSubscription")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn get_subscription_payment_info<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue4<SubscriptionStatus, u64, BigUint<Env::Api>, BigUint<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSubscriptionPaymentInfo")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn get_subscription_state<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue4<SubscriptionStatus, u64, u64, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSubscriptionState")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn get_pending_usage<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<UsageReport>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPendingUsage")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn get_service_deactivation<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ServiceDeactivation>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceDeactivation")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    /// The scheduled price change that has not taken effect yet, if any. 
+    pub fn get_pending_price_change<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<PriceChange<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPendingPriceChange")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn is_service_transferable<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isServiceTransferable")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    /// A non-zero `deficit` means the accounting invariant is broken. 
+    pub fn get_reconciliation<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        token: Arg0,
+        nonce: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Reconciliation<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReconciliation")
+            .argument(&token)
+            .argument(&nonce)
+            .original_result()
+    }
+
+    pub fn get_schema_migration(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<SchemaMigration>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSchemaMigration")
+            .original_result()
+    }
+
+    pub fn get_governance_proposal<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        proposal_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<GovernanceProposal<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGovernanceProposal")
+            .argument(&proposal_id)
+            .original_result()
+    }
+
+    pub fn get_pending_governance_proposals(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<u64, GovernanceProposal<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPendingGovernanceProposals")
+            .original_result()
+    }
+
+    pub fn get_pending_transfer<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPendingTransfer")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn get_dispute<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<Dispute<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDispute")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    /// Retained settlements, oldest first. The lifetime total charged is 
+    /// the subscription's `total_spent`. 
+    pub fn get_payment_history<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+        offset: Arg1,
+        limit: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Settlement<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPaymentHistory")
+            .argument(&sub_id)
+            .argument(&offset)
+            .argument(&limit)
+            .original_result()
+    }
+
+    pub fn get_invoice<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        provider: Arg0,
+        number: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Invoice<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getInvoice")
+            .argument(&provider)
+            .argument(&number)
+            .original_result()
+    }
+
+    pub fn get_service_stats<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, SubscriberStats> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceStats")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn get_provider_stats<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, SubscriberStats> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProviderStats")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn can_subscribe<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        service_id: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("canSubscribe")
+            .argument(&service_id)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_service_allowlist<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceAllowlist")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn get_service_blocklist<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceBlocklist")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn get_user_role<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u8> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUserRole")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn last_service_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLastServiceId")
+            .original_result()
+    }
+
+    pub fn last_subscription_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLastSubscriptionId")
+            .original_result()
+    }
+
+    pub fn usage_dispute_window(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getUsageDisputeWindow")
+            .original_result()
+    }
+
+    pub fn service_metering<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MeteringConfig<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceMetering")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn subscription_metering<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MeteringConfig<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSubscriptionMetering")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn service_commitment<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, CommitmentTerms<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceCommitment")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn service_metadata<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ServiceMetadata<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceMetadata")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn total_liabilities<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        token: Arg0,
+        nonce: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTotalLiabilities")
+            .argument(&token)
+            .argument(&nonce)
+            .original_result()
+    }
+
+    pub fn service_bond_amount(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceBondAmount")
+            .original_result()
+    }
+
+    pub fn bond_cooldown(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBondCooldown")
+            .original_result()
+    }
+
+    pub fn service_bond<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceBond")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn service_deactivated_block<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceDeactivatedBlock")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn service_delisted<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isServiceDelisted")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn moderators(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getModerators")
+            .original_result()
+    }
+
+    pub fn governance_delay(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGovernanceDelay")
+            .original_result()
+    }
+
+    pub fn last_governance_proposal_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLastGovernanceProposalId")
+            .original_result()
+    }
+
+    pub fn min_price_notice(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMinPriceNotice")
+            .original_result()
+    }
+
+    pub fn subscription_members<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSubscriptionMembers")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn member_subscriptions<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMemberSubscriptions")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn bundle_services<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        bundle_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBundleServices")
+            .argument(&bundle_id)
+            .original_result()
+    }
+
+    pub fn service_bundles<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceBundles")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn service_hook<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceHook")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn hook_failures<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getHookFailures")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn service_access_mode<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, AccessMode> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceAccessMode")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn arbiter(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getArbiter")
+            .original_result()
+    }
+
+    pub fn service_arbiter<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceArbiter")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn dispute_timeout(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDisputeTimeout")
+            .original_result()
+    }
+
+    pub fn dispute_default_provider_share(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDisputeDefaultProviderShare")
+            .original_result()
+    }
+
+    pub fn held_payment<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getHeldPayment")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn revival_approved<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isRevivalApproved")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    /// Number of settlements ever recorded for the subscription. 
+    pub fn settlement_count<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        sub_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSettlementCount")
+            .argument(&sub_id)
+            .original_result()
+    }
+
+    pub fn last_invoice_number<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        provider: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLastInvoiceNumber")
+            .argument(&provider)
+            .original_result()
+    }
+
+    pub fn service_total_collected<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceTotalCollected")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn provider_total_collected<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+        token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProviderTotalCollected")
+            .argument(&address)
+            .argument(&token)
+            .original_result()
+    }
+
+    /// Subscriber funds held by the contract: unspent balances, escrowed 
+    /// termination fees and dispute escrows. 
+    pub fn service_committed_escrow<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        service_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getServiceCommittedEscrow")
+            .argument(&service_id)
+            .original_result()
+    }
+
+    pub fn provider_committed_escrow<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+        token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProviderCommittedEscrow")
+            .argument(&address)
+            .argument(&token)
+            .original_result()
+    }
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub enum GovernanceAction<Api>
+where
+    Api: ManagedTypeApi,
+{
+    SetUsageDisputeWindow(u64),
+    SetArbiter(ManagedAddress<Api>),
+    SetServiceArbiter(u64, Option<ManagedAddress<Api>>),
+    SetDisputeTimeout(u64),
+    SetDisputeDefaultProviderShare(u64),
+    SetMinPriceNotice(u64),
+    SetGovernanceDelay(u64),
+    SetServiceBond(BigUint<Api>),
+    SetModerator(ManagedAddress<Api>, bool),
+    SetBondCooldown(u64),
+    SweepSurplus(EgldOrEsdtTokenIdentifier<Api>, u64),
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeactivationMode {
+    StopNewSignups,
+    StopRenewals,
+    TerminateNow,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccessMode {
+    Public,
+    Allowlist,
+    Blocklist,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
+pub struct Service<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub id: u64,
+    pub provider: ManagedAddress<Api>,
+    pub name: ManagedBuffer<Api>,
+    pub description: ManagedBuffer<Api>,
+    pub token_identifier: EgldOrEsdtTokenIdentifier<Api>,
+    pub amount_per_cycle: BigUint<Api>,
+    pub frequency_in_blocks: u64,
+    pub active: bool,
+    pub token_nonce: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubscriptionStatus {
+    None,
+    Active,
+    PendingUserCancel,
+    PendingProviderCancel,
+    CancelledByUser,
+    CancelledByProvider,
+    CancelledInsufficientFunds,
+    Completed,
+    Disputed,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct Subscription<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub id: u64,
+    pub service_id: u64,
+    pub client: ManagedAddress<Api>,
+    pub vendor: ManagedAddress<Api>,
+    pub token_identifier: EgldOrEsdtTokenIdentifier<Api>,
+    pub amount_per_cycle: BigUint<Api>,
+    pub frequency_in_blocks: u64,
+    pub remaining_balance: BigUint<Api>,
+    pub last_payment_block: u64,
+    pub next_payment_block: u64,
+    pub status: SubscriptionStatus,
+    pub cancel_effective_block: u64,
+    pub max_total_spend: BigUint<Api>,
+    pub max_cycles: u64,
+    pub end_block: u64,
+    pub total_spent: BigUint<Api>,
+    pub cycles_paid: u64,
+    pub commitment_end_block: u64,
+    pub early_termination_fee: BigUint<Api>,
+    pub escrowed_termination_fee: BigUint<Api>,
+    pub price_epoch: u64,
+    pub seats: u64,
+    pub token_nonce: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct SubscriptionSummary<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub id: u64,
+    pub service_id: u64,
+    pub client: ManagedAddress<Api>,
+    pub vendor: ManagedAddress<Api>,
+    pub token_identifier: EgldOrEsdtTokenIdentifier<Api>,
+    pub status: SubscriptionStatus,
+    pub total_spent: BigUint<Api>,
+    pub cycles_paid: u64,
+    pub cancel_effective_block: u64,
+    pub archived: bool,
+    pub token_nonce: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
+pub struct UsageReport {
+    pub cycle_block: u64,
+    pub usage: u64,
+    pub reported_block: u64,
+    pub attested: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct ServiceDeactivation {
+    pub mode: DeactivationMode,
+    pub next_index: usize,
+    pub completed: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct PriceChange<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub amount_per_cycle: BigUint<Api>,
+    pub frequency_in_blocks: u64,
+    pub effective_block: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Reconciliation<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub liabilities: BigUint<Api>,
+    pub balance: BigUint<Api>,
+    pub surplus: BigUint<Api>,
+    pub deficit: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct SchemaMigration {
+    pub from_version: u32,
+    pub next_service_id: u64,
+    pub last_service_id: u64,
+    pub next_subscription_id: u64,
+    pub last_subscription_id: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct GovernanceProposal<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub action: GovernanceAction<Api>,
+    pub proposed_block: u64,
+    pub executable_block: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
+pub struct Dispute<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub opened_block: u64,
+    pub deadline_block: u64,
+    pub escrow: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Settlement<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub block: u64,
+    pub timestamp: u64,
+    pub amount: BigUint<Api>,
+    pub kind: SettlementKind,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettlementKind {
+    Initial,
+    Cycle,
+    Refund,
+    Fee,
+    Proration,
+    Shortfall,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Invoice<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub subscription_id: u64,
+    pub service_id: u64,
+    pub amount: BigUint<Api>,
+    pub token_identifier: EgldOrEsdtTokenIdentifier<Api>,
+    pub block: u64,
+    pub token_nonce: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Default, PartialEq, Debug)]
+pub struct SubscriberStats {
+    pub active_subscribers: u64,
+    pub lifetime_subscriptions: u64,
+    pub cancelled_by_user: u64,
+    pub cancelled_by_provider: u64,
+    pub cancelled_insufficient_funds: u64,
+    pub completed: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
+pub struct MeteringConfig<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub unit_price: BigUint<Api>,
+    pub usage_cap_per_cycle: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
+pub struct CommitmentTerms<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub min_cycles: u64,
+    pub early_termination_fee: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct ServiceMetadata<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub category_id: u32,
+    pub tags: ManagedVec<Api, ManagedBuffer<Api>>,
+    pub details_uri: ManagedBuffer<Api>,
+    pub content_hash: ManagedByteArray<Api, 32usize>,
+    pub max_subscribers: u64,
+}
@@ -0,0 +1,38 @@
+multiversx_sc::imports!();
+
+use crate::scheduled_payments_proxy;
+
+/// Gates features of another contract on subscriptions held in the
+/// scheduled payments contract. The checks are synchronous calls, so the
+/// subscription contract must live in the same shard as the caller.
+#[multiversx_sc::module]
+pub trait SubscriptionAccessModule {
+    fn require_subscribed(&self, address: &ManagedAddress, service_id: u64) {
+        require!(
+            self.is_subscribed_to(address, service_id),
+            "No active subscription"
+        );
+    }
+
+    fn is_subscribed_to(&self, address: &ManagedAddress, service_id: u64) -> bool {
+        self.tx()
+            .to(self.subscription_contract().get())
+            .typed(scheduled_payments_proxy::SubscriptionContractProxy)
+            .is_subscribed(address, service_id)
+            .returns(ReturnsResult)
+            .sync_call_readonly()
+    }
+
+    fn access_until(&self, address: &ManagedAddress, service_id: u64) -> u64 {
+        self.tx()
+            .to(self.subscription_contract().get())
+            .typed(scheduled_payments_proxy::SubscriptionContractProxy)
+            .has_access_until(address, service_id)
+            .returns(ReturnsResult)
+            .sync_call_readonly()
+    }
+
+    #[view(getSubscriptionContract)]
+    #[storage_mapper("subscriptionContract")]
+    fn subscription_contract(&self) -> SingleValueMapper<ManagedAddress>;
+}
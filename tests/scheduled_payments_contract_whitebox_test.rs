@@ -0,0 +1,2161 @@
+use multiversx_sc_scenario::imports::*;
+use scheduled_payments_contract::*;
+use scheduled_payments_contract_v1::SubscriptionContract as _;
+
+const OWNER: TestAddress = TestAddress::new("owner");
+const PROVIDER: TestAddress = TestAddress::new("provider");
+const CLIENT: TestAddress = TestAddress::new("client");
+const ARBITER: TestAddress = TestAddress::new("arbiter");
+const LP_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("LP-123456");
+const LP_NONCE: u64 = 3;
+const SC_ADDRESS: TestSCAddress = TestSCAddress::new("scheduled-payments");
+const CODE_PATH: MxscPath = MxscPath::new("output/scheduled-payments-contract.mxsc.json");
+const LEGACY_CODE_PATH: MxscPath =
+    MxscPath::new("legacy/scheduled-payments-v1/output/scheduled-payments-contract-v1.mxsc.json");
+
+const PRICE: u64 = 100;
+const FREQUENCY: u64 = 10;
+const GOVERNANCE_DELAY: u64 = 14_400;
+
+fn world() -> ScenarioWorld {
+    let mut blockchain = ScenarioWorld::new();
+    blockchain.register_contract(CODE_PATH, scheduled_payments_contract::ContractBuilder);
+    blockchain
+}
+
+/// Deploys the contract, registers both parties and creates one EGLD service.
+fn setup() -> ScenarioWorld {
+    let mut world = world();
+    world.account(OWNER).nonce(1);
+    world.account(PROVIDER).nonce(1).balance(0);
+    world.account(CLIENT).nonce(1).balance(10_000);
+    world.current_block().block_nonce(1);
+
+    world
+        .tx()
+        .from(OWNER)
+        .raw_deploy()
+        .code(CODE_PATH)
+        .new_address(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.init(OptionalValue::Some(0));
+        });
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.register_as_provider();
+            let service_id = sc.create_service(
+                ManagedBuffer::from("service"),
+                ManagedBuffer::from("description"),
+                BigUint::from(PRICE),
+                FREQUENCY,
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+            assert_eq!(service_id, 1);
+        });
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.register_as_user();
+        });
+
+    world
+}
+
+fn subscribe(world: &mut ScenarioWorld, deposit: u64) {
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(deposit)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(
+                1,
+                OptionalValue::None,
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+        });
+}
+
+fn trigger_payment(world: &mut ScenarioWorld, sub_id: u64) {
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.trigger_payment(sub_id);
+        });
+}
+
+#[test]
+fn subscribe_and_trigger_payment_test() {
+    let mut world = setup();
+    subscribe(&mut world, 250);
+    world.check_account(PROVIDER).balance(0);
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Payment cycle not reached yet"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.trigger_payment(1);
+        });
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(PRICE);
+
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(2 * PRICE);
+    world.check_account(CLIENT).balance(10_000 - 2 * PRICE);
+    assert_reconciled(&mut world, EgldOrEsdtTokenIdentifier::egld, 0);
+}
+
+/// Proposes and executes a settings change. Test deployments have no
+/// governance delay, so actions apply at once.
+fn govern(world: &mut ScenarioWorld, action: fn() -> GovernanceAction<DebugApi>) {
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let proposal_id = sc.propose_governance_action(action());
+            sc.execute_governance_action(proposal_id);
+        });
+}
+
+/// Everything the contract holds in the token is owed to someone.
+fn assert_reconciled(
+    world: &mut ScenarioWorld,
+    token: fn() -> EgldOrEsdtTokenIdentifier<DebugApi>,
+    nonce: u64,
+) {
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let reconciliation = sc.get_reconciliation(token(), nonce);
+            assert_eq!(reconciliation.surplus, BigUint::zero());
+            assert_eq!(reconciliation.deficit, BigUint::zero());
+        });
+}
+
+fn setup_metered(unit_price: u64, usage_cap: u64) -> ScenarioWorld {
+    let mut world = setup();
+    govern(&mut world, || GovernanceAction::SetUsageDisputeWindow(5));
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_service_metering(1, BigUint::from(unit_price), usage_cap);
+        });
+    world
+}
+
+fn report_usage(world: &mut ScenarioWorld, sub_id: u64, usage: u64) {
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.report_usage(sub_id, usage, OptionalValue::None);
+        });
+}
+
+#[test]
+fn metered_usage_is_capped_and_settled_after_window_test() {
+    let mut world = setup_metered(3, 20);
+    subscribe(&mut world, 1_000);
+
+    world.current_block().block_nonce(2);
+    report_usage(&mut world, 1, 50);
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Previous usage report not settled"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.report_usage(1, 10, OptionalValue::None);
+        });
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(PRICE);
+    world.check_account(SC_ADDRESS).balance(1_000 - PRICE);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert!(sc.get_pending_usage(1).into_option().is_none());
+            let sub = sc.subscriptions(1).get();
+            assert_eq!(sub.remaining_balance, BigUint::from(1_000 - 2 * PRICE - 3 * 20));
+        });
+}
+
+#[test]
+fn metered_usage_waits_for_dispute_window_test() {
+    let mut world = setup_metered(3, 20);
+    subscribe(&mut world, 1_000);
+
+    world.current_block().block_nonce(8);
+    report_usage(&mut world, 1, 10);
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+    world.current_block().block_nonce(31);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(3 * PRICE + 3 * 10);
+}
+
+#[test]
+fn disputed_usage_is_not_charged_test() {
+    let mut world = setup_metered(3, 20);
+    subscribe(&mut world, 1_000);
+
+    world.current_block().block_nonce(2);
+    report_usage(&mut world, 1, 10);
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.dispute_usage(1);
+        });
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(2 * PRICE);
+}
+
+#[test]
+fn zero_usage_report_settles_and_shortfall_is_recorded_test() {
+    let mut world = setup_metered(3, 20);
+    subscribe(&mut world, 250);
+
+    world.current_block().block_nonce(2);
+    report_usage(&mut world, 1, 0);
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(PRICE);
+
+    world.current_block().block_nonce(12);
+    report_usage(&mut world, 1, 20);
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(2 * PRICE + 50);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let sub = sc.subscriptions(1).get();
+            assert_eq!(sub.status, SubscriptionStatus::CancelledInsufficientFunds);
+            let history: Vec<_> = sc.get_payment_history(1, 0, 10).into_iter().collect();
+            let shortfall = history
+                .iter()
+                .find(|settlement| settlement.kind == SettlementKind::Shortfall)
+                .unwrap();
+            assert_eq!(shortfall.amount, PRICE + 3 * 20 - 50);
+        });
+}
+
+#[test]
+fn zero_usage_report_does_not_block_cancellation_test() {
+    let mut world = setup_metered(3, 20);
+    subscribe(&mut world, 250);
+    world.current_block().block_nonce(2);
+    report_usage(&mut world, 1, 0);
+    cancel_by_user(&mut world, 1);
+
+    world.current_block().block_nonce(11);
+    finalize_cancellation(&mut world, 1);
+    world.check_account(CLIENT).balance(10_000 - PRICE);
+}
+
+fn subscribe_with_limits(
+    world: &mut ScenarioWorld,
+    deposit: u64,
+    max_total_spend: u64,
+    max_cycles: u64,
+    end_block: u64,
+) {
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(deposit)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(
+                1,
+                OptionalValue::Some(BigUint::from(max_total_spend)),
+                OptionalValue::Some(max_cycles),
+                OptionalValue::Some(end_block),
+            );
+        });
+}
+
+fn check_status(world: &mut ScenarioWorld, sub_id: u64, expected_status: SubscriptionStatus) {
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.subscriptions(sub_id).get().status, expected_status);
+        });
+}
+
+#[test]
+fn max_cycles_completes_subscription_test() {
+    let mut world = setup();
+    subscribe_with_limits(&mut world, 1_000, 0, 2, 0);
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+
+    check_status(&mut world, 1, SubscriptionStatus::Completed);
+    world.check_account(PROVIDER).balance(2 * PRICE);
+    world.check_account(CLIENT).balance(10_000 - 2 * PRICE);
+}
+
+#[test]
+fn final_cycle_usage_is_charged_when_limit_completes_subscription_test() {
+    let mut world = setup_metered(3, 20);
+    subscribe_with_limits(&mut world, 1_000, 0, 2, 0);
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+
+    world.current_block().block_nonce(12);
+    report_usage(&mut world, 1, 10);
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+
+    check_status(&mut world, 1, SubscriptionStatus::Completed);
+    world.check_account(PROVIDER).balance(2 * PRICE + 3 * 10);
+    world.check_account(CLIENT).balance(10_000 - 2 * PRICE - 3 * 10);
+}
+
+#[test]
+fn usage_is_capped_by_spending_limit_test() {
+    let mut world = setup_metered(3, 20);
+    subscribe_with_limits(&mut world, 1_000, 250, 0, 0);
+    world.current_block().block_nonce(2);
+    report_usage(&mut world, 1, 20);
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    check_status(&mut world, 1, SubscriptionStatus::Active);
+    world.check_account(PROVIDER).balance(PRICE);
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.held_payment(1).get(), 150u64);
+        });
+}
+
+#[test]
+fn spending_cap_and_end_block_complete_subscription_test() {
+    let mut world = setup();
+    subscribe_with_limits(&mut world, 1_000, 250, 0, 0);
+    subscribe_with_limits(&mut world, 1_000, 0, 0, 15);
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    trigger_payment(&mut world, 2);
+    check_status(&mut world, 1, SubscriptionStatus::Active);
+    check_status(&mut world, 2, SubscriptionStatus::Active);
+
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+    trigger_payment(&mut world, 2);
+    check_status(&mut world, 1, SubscriptionStatus::Completed);
+    check_status(&mut world, 2, SubscriptionStatus::Completed);
+    world.check_account(PROVIDER).balance(4 * PRICE);
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(1_000)
+        .returns(ExpectError(4, "Spending cap must cover at least one cycle"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(
+                1,
+                OptionalValue::Some(BigUint::from(PRICE - 1)),
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+        });
+}
+
+fn setup_committed(min_cycles: u64, early_termination_fee: u64) -> ScenarioWorld {
+    let mut world = setup();
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_service_commitment(1, min_cycles, BigUint::from(early_termination_fee));
+        });
+    world
+}
+
+fn cancel_by_user(world: &mut ScenarioWorld, sub_id: u64) {
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.cancel_subscription_by_user(sub_id);
+        });
+}
+
+fn finalize_cancellation(world: &mut ScenarioWorld, sub_id: u64) {
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.finalize_cancellation(sub_id);
+        });
+}
+
+#[test]
+fn early_termination_fee_is_escrowed_and_paid_test() {
+    let mut world = setup_committed(3, 150);
+    subscribe(&mut world, 1_000);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let (_, _, _, commitment_end_block) = sc.get_subscription_state(1).into_tuple();
+            assert_eq!(commitment_end_block, 1 + 3 * FREQUENCY);
+        });
+
+    world.current_block().block_nonce(2);
+    cancel_by_user(&mut world, 1);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let sub = sc.subscriptions(1).get();
+            assert_eq!(sub.escrowed_termination_fee, BigUint::from(150u64));
+            assert_eq!(sub.remaining_balance, BigUint::from(1_000 - PRICE - 150));
+        });
+
+    world.current_block().block_nonce(11);
+    finalize_cancellation(&mut world, 1);
+    world.check_account(PROVIDER).balance(PRICE + 150);
+    world.check_account(CLIENT).balance(10_000 - PRICE - 150);
+}
+
+#[test]
+fn no_fee_after_commitment_ends_test() {
+    let mut world = setup_committed(2, 150);
+    subscribe(&mut world, 1_000);
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    cancel_by_user(&mut world, 1);
+
+    world.current_block().block_nonce(21);
+    finalize_cancellation(&mut world, 1);
+    world.check_account(PROVIDER).balance(2 * PRICE);
+    world.check_account(CLIENT).balance(10_000 - 2 * PRICE);
+}
+
+fn setup_disputed() -> ScenarioWorld {
+    let mut world = setup();
+    world.account(ARBITER).nonce(1);
+    govern(&mut world, || GovernanceAction::SetArbiter(ARBITER.to_managed_address()));
+    govern(&mut world, || GovernanceAction::SetDisputeTimeout(50));
+    subscribe(&mut world, 1_000);
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.open_dispute(1);
+            assert_eq!(sc.disputes(1).get().escrow, PRICE);
+            assert_eq!(sc.held_payment(1).get(), 0u64);
+        });
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(0);
+    world
+}
+
+#[test]
+fn arbiter_splits_dispute_escrow_test() {
+    let mut world = setup_disputed();
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Only arbiter can resolve"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.resolve_dispute(1, 10_000);
+        });
+
+    world
+        .tx()
+        .from(ARBITER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.resolve_dispute(1, 2_500);
+        });
+
+    world.check_account(PROVIDER).balance(3 * PRICE / 4);
+    world.check_account(CLIENT).balance(10_000 - 1_000 + 3 * PRICE * 3 / 4);
+    check_status(&mut world, 1, SubscriptionStatus::Active);
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let provider = PROVIDER.to_managed_address();
+            assert_eq!(sc.last_invoice_number(&provider).get(), 1);
+            assert_eq!(sc.get_invoice(provider, 1).amount, 3 * PRICE / 4);
+        });
+    assert_reconciled(&mut world, EgldOrEsdtTokenIdentifier::egld, 0);
+}
+
+#[test]
+fn expired_dispute_uses_default_share_test() {
+    let mut world = setup_disputed();
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Dispute not expired yet"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.expire_dispute(1);
+        });
+
+    world.current_block().block_nonce(51);
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.expire_dispute(1);
+        });
+
+    world.check_account(PROVIDER).balance(3 * PRICE / 2);
+    world.check_account(CLIENT).balance(10_000 - 1_000 + 3 * PRICE / 2);
+}
+
+#[test]
+fn paginated_subscription_views_test() {
+    let mut world = setup();
+    for _ in 0..3 {
+        subscribe(&mut world, 150);
+    }
+    cancel_by_user(&mut world, 2);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let client = CLIENT.to_managed_address();
+            assert_eq!(sc.get_user_subscription_count(client.clone()), 3);
+
+            let page = sc.get_user_subscriptions_page(
+                client.clone(),
+                1,
+                5,
+                SubscriptionStatus::None,
+                OptionalValue::None,
+            );
+            let ids: Vec<u64> = page.into_iter().map(|sub| sub.id).collect();
+            assert_eq!(ids, vec![2, 3]);
+
+            let page = sc.get_user_subscriptions_page(
+                client,
+                0,
+                3,
+                SubscriptionStatus::Active,
+                OptionalValue::None,
+            );
+            let ids: Vec<u64> = page.into_iter().map(|sub| sub.id).collect();
+            assert_eq!(ids, vec![1, 3]);
+
+            let page = sc.get_services_page(
+                0,
+                10,
+                true,
+                OptionalValue::Some(EgldOrEsdtTokenIdentifier::esdt("USDC-123456")),
+            );
+            assert_eq!(page.len(), 0);
+            assert_eq!(sc.get_services_page(0, 10, true, OptionalValue::None).len(), 1);
+        });
+}
+
+#[test]
+fn terminated_subscription_is_archived_test() {
+    let mut world = setup();
+    subscribe(&mut world, 150);
+    subscribe(&mut world, 1_000);
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Subscription not terminated"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.archive_subscription(1);
+        });
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+
+    let stranger = TestAddress::new("stranger");
+    world.account(stranger).nonce(1);
+    world
+        .tx()
+        .from(stranger)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Only subscriber, provider or owner can archive"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.archive_subscription(1);
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.archive_subscription(1);
+        });
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Subscription archived"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_revival_approval(1, true);
+        });
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(PRICE)
+        .returns(ExpectError(4, "Subscription archived"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.revive_subscription(1);
+        });
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let client = CLIENT.to_managed_address();
+            let active = sc.get_user_active_subscriptions(client.clone());
+            assert_eq!(active.len(), 1);
+            assert_eq!(active.get(0), 2);
+            assert!(sc.subscriptions(1).is_empty());
+
+            let summary = sc.get_subscription_summary(1);
+            assert!(summary.archived);
+            assert_eq!(summary.status, SubscriptionStatus::CancelledInsufficientFunds);
+            assert_eq!(summary.total_spent, BigUint::from(PRICE));
+            assert_eq!(summary.cancel_effective_block, 11);
+
+            let archived = sc.get_subscription(1);
+            assert_eq!(archived.status, SubscriptionStatus::CancelledInsufficientFunds);
+            assert_eq!(archived.remaining_balance, BigUint::zero());
+            let (status, next_payment_block, _, _) = sc.get_subscription_state(1).into_tuple();
+            assert_eq!(status, SubscriptionStatus::CancelledInsufficientFunds);
+            assert_eq!(next_payment_block, 0);
+            let (status, _, _, _) = sc.get_subscription_payment_info(1).into_tuple();
+            assert_eq!(status, SubscriptionStatus::CancelledInsufficientFunds);
+            assert_eq!(sc.get_payment_history(1, 0, 10).len(), 3);
+
+            let history: Vec<u64> = sc
+                .get_user_subscription_history(client.clone(), 0, 10)
+                .into_iter()
+                .map(|summary| summary.id)
+                .collect();
+            assert_eq!(history, vec![1, 2]);
+            let page = sc.get_user_subscriptions_page(
+                client,
+                0,
+                10,
+                SubscriptionStatus::None,
+                OptionalValue::None,
+            );
+            assert_eq!(page.len(), 1);
+        });
+}
+
+fn cancel_by_provider(world: &mut ScenarioWorld, sub_id: u64) {
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.cancel_subscription_by_provider(sub_id);
+        });
+}
+
+#[test]
+fn top_up_does_not_revive_cancelled_subscription_test() {
+    let mut world = setup();
+    subscribe(&mut world, 1_000);
+    cancel_by_provider(&mut world, 1);
+
+    world.current_block().block_nonce(11);
+    finalize_cancellation(&mut world, 1);
+    check_status(&mut world, 1, SubscriptionStatus::CancelledByProvider);
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(500)
+        .returns(ExpectError(4, "Subscription is terminated"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.top_up(1);
+        });
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Invalid status transition"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.cancel_subscription_by_user(1);
+        });
+}
+
+#[test]
+fn provider_approved_revival_test() {
+    let mut world = setup();
+    subscribe(&mut world, 1_000);
+    cancel_by_provider(&mut world, 1);
+    world.current_block().block_nonce(11);
+    finalize_cancellation(&mut world, 1);
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(300)
+        .returns(ExpectError(4, "Revival not approved"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.revive_subscription(1);
+        });
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_revival_approval(1, true);
+        });
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Subscription approved for revival"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.archive_subscription(1);
+        });
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(300)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.revive_subscription(1);
+        });
+
+    check_status(&mut world, 1, SubscriptionStatus::Active);
+    world.check_account(PROVIDER).balance(PRICE);
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let sub = sc.subscriptions(1).get();
+            assert_eq!(sub.remaining_balance, BigUint::from(300 - PRICE));
+            assert_eq!(sub.next_payment_block, 11 + FREQUENCY);
+        });
+}
+
+#[test]
+fn revoke_cancellation_test() {
+    let mut world = setup_committed(3, 150);
+    subscribe(&mut world, 1_000);
+    world.current_block().block_nonce(5);
+    cancel_by_user(&mut world, 1);
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Only the requesting party can revoke"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.revoke_cancellation(1);
+        });
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.revoke_cancellation(1);
+        });
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let sub = sc.subscriptions(1).get();
+            assert_eq!(sub.status, SubscriptionStatus::Active);
+            assert_eq!(sub.remaining_balance, BigUint::from(1_000 - PRICE));
+            assert_eq!(sub.escrowed_termination_fee, BigUint::zero());
+            assert_eq!(sub.next_payment_block, 1 + FREQUENCY);
+        });
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(PRICE);
+}
+
+#[test]
+fn revoke_after_effective_block_fails_test() {
+    let mut world = setup();
+    subscribe(&mut world, 1_000);
+    cancel_by_provider(&mut world, 1);
+
+    world.current_block().block_nonce(11);
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Cancellation already effective"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.revoke_cancellation(1);
+        });
+}
+
+#[test]
+fn allowlisted_service_rejects_other_subscribers_test() {
+    let mut world = setup();
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_service_access_mode(1, AccessMode::Allowlist);
+        });
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(500)
+        .returns(ExpectError(4, "Not allowed to subscribe to this service"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(1, OptionalValue::None, OptionalValue::None, OptionalValue::None);
+        });
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(CLIENT.to_managed_address());
+            sc.add_to_service_allowlist(1, addresses);
+        });
+    subscribe(&mut world, 500);
+    check_status(&mut world, 1, SubscriptionStatus::Active);
+}
+
+#[test]
+fn blocklisting_cancels_existing_subscription_test() {
+    let mut world = setup();
+    subscribe(&mut world, 500);
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_service_access_mode(1, AccessMode::Blocklist);
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(CLIENT.to_managed_address());
+            sc.add_to_service_blocklist(1, true, addresses);
+        });
+
+    check_status(&mut world, 1, SubscriptionStatus::PendingProviderCancel);
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert!(!sc.can_subscribe(1, CLIENT.to_managed_address()));
+        });
+}
+
+fn set_metadata(world: &mut ScenarioWorld, category_id: u32, max_subscribers: u64, tags: &[&str]) {
+    let tags: Vec<String> = tags.iter().map(|tag| tag.to_string()).collect();
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let mut tag_args = MultiValueEncoded::new();
+            for tag in &tags {
+                tag_args.push(ManagedBuffer::from(tag.as_str()));
+            }
+            sc.set_service_metadata(
+                1,
+                category_id,
+                ManagedBuffer::from("ipfs://details"),
+                ManagedByteArray::new_from_bytes(&[7u8; 32]),
+                max_subscribers,
+                tag_args,
+            );
+        });
+}
+
+#[test]
+fn services_are_listed_by_category_tag_and_token_test() {
+    let mut world = setup();
+    set_metadata(&mut world, 3, 0, &["api", "storage"]);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.get_active_services_by_category(3, 0, 10).len(), 1);
+            assert_eq!(sc.get_active_services_by_category(4, 0, 10).len(), 0);
+            assert_eq!(sc.get_active_services_by_tag(ManagedBuffer::from("api"), 0, 10).len(), 1);
+            assert_eq!(
+                sc.get_active_services_by_token(EgldOrEsdtTokenIdentifier::egld(), 0, 10).len(),
+                1
+            );
+        });
+
+    set_metadata(&mut world, 4, 0, &["storage"]);
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.deactivate_service(1, OptionalValue::None);
+        });
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.get_category_service_count(3), 0);
+            assert_eq!(sc.get_category_service_count(4), 0);
+            assert_eq!(sc.get_tag_service_count(ManagedBuffer::from("api")), 0);
+            assert_eq!(sc.get_token_service_count(EgldOrEsdtTokenIdentifier::egld()), 0);
+        });
+}
+
+#[test]
+fn max_subscribers_cap_is_enforced_test() {
+    let mut world = setup();
+    set_metadata(&mut world, 1, 1, &[]);
+    subscribe(&mut world, 500);
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(500)
+        .returns(ExpectError(4, "Service is full"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(1, OptionalValue::None, OptionalValue::None, OptionalValue::None);
+        });
+}
+
+#[test]
+fn revenue_and_churn_stats_test() {
+    let mut world = setup();
+    subscribe(&mut world, 250);
+    subscribe(&mut world, 300);
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    cancel_by_user(&mut world, 2);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let stats = sc.get_service_stats(1);
+            assert_eq!(stats.active_subscribers, 2);
+            assert_eq!(stats.lifetime_subscriptions, 2);
+            assert_eq!(sc.service_total_collected(1).get(), PRICE);
+            assert_eq!(sc.service_committed_escrow(1).get(), 550 - PRICE);
+        });
+
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+    finalize_cancellation(&mut world, 2);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let expected = SubscriberStats {
+                active_subscribers: 0,
+                lifetime_subscriptions: 2,
+                cancelled_by_user: 1,
+                cancelled_by_provider: 0,
+                cancelled_insufficient_funds: 1,
+                completed: 0,
+            };
+            assert_eq!(sc.get_service_stats(1), expected);
+            assert_eq!(sc.get_provider_stats(PROVIDER.to_managed_address()), expected);
+            assert_eq!(
+                sc.provider_total_collected(&PROVIDER.to_managed_address(), &EgldOrEsdtTokenIdentifier::egld())
+                    .get(),
+                3 * PRICE
+            );
+            assert_eq!(sc.service_committed_escrow(1).get(), 0u64);
+        });
+    world.check_account(PROVIDER).balance(3 * PRICE);
+}
+
+#[test]
+fn payment_history_is_recorded_test() {
+    let mut world = setup();
+    subscribe(&mut world, 250);
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    cancel_by_user(&mut world, 1);
+    world.current_block().block_nonce(21);
+    finalize_cancellation(&mut world, 1);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let history: Vec<_> = sc.get_payment_history(1, 0, 10).into_iter().collect();
+            let kinds: Vec<_> = history.iter().map(|settlement| settlement.kind).collect();
+            assert_eq!(
+                kinds,
+                [
+                    SettlementKind::Initial,
+                    SettlementKind::Cycle,
+                    SettlementKind::Refund
+                ]
+            );
+            assert_eq!(history[0].block, 1);
+            assert_eq!(history[1].block, 11);
+            assert_eq!(history[2].block, 21);
+            assert_eq!(history[2].amount, 50u64);
+
+            let page: Vec<_> = sc.get_payment_history(1, 1, 1).into_iter().collect();
+            assert_eq!(page, [history[1].clone()]);
+        });
+}
+
+#[test]
+fn payment_history_keeps_most_recent_settlements_test() {
+    let mut world = setup();
+    subscribe(&mut world, 4_000);
+    for cycle in 1..=35 {
+        world.current_block().block_nonce(1 + cycle * FREQUENCY);
+        trigger_payment(&mut world, 1);
+    }
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.settlement_count(1).get(), 36);
+            let history: Vec<_> = sc.get_payment_history(1, 0, 100).into_iter().collect();
+            assert_eq!(history.len(), 32);
+            assert_eq!(history[0].block, 1 + 4 * FREQUENCY);
+            assert_eq!(history[31].block, 1 + 35 * FREQUENCY);
+        });
+}
+
+#[test]
+fn payments_get_sequential_invoice_numbers_test() {
+    let mut world = setup();
+    subscribe(&mut world, 250);
+    subscribe(&mut world, 250);
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 2);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let provider = PROVIDER.to_managed_address();
+            assert_eq!(sc.last_invoice_number(&provider).get(), 1);
+
+            let invoice = sc.get_invoice(provider, 1);
+            assert_eq!(invoice.subscription_id, 2);
+            assert_eq!(invoice.service_id, 1);
+            assert_eq!(invoice.amount, PRICE);
+            assert_eq!(invoice.token_identifier, EgldOrEsdtTokenIdentifier::egld());
+            assert_eq!(invoice.block, 11);
+
+            let kinds: Vec<_> = sc
+                .get_payment_history(2, 0, 10)
+                .into_iter()
+                .map(|settlement| settlement.kind)
+                .collect();
+            assert_eq!(kinds, [SettlementKind::Initial, SettlementKind::Cycle]);
+        });
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Invoice not found"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.get_invoice(PROVIDER.to_managed_address(), 2);
+        });
+}
+
+#[test]
+fn meta_esdt_service_and_multi_top_up_test() {
+    let lp_client = TestAddress::new("lp-client");
+    let mut world = setup();
+    world
+        .account(lp_client)
+        .nonce(1)
+        .esdt_nft_balance(LP_TOKEN, LP_NONCE, 1_000, ())
+        .esdt_nft_balance(LP_TOKEN, LP_NONCE + 1, 1_000, ());
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.create_service(
+                ManagedBuffer::from("lp service"),
+                ManagedBuffer::from("description"),
+                BigUint::from(PRICE),
+                FREQUENCY,
+                OptionalValue::Some(EgldOrEsdtTokenIdentifier::esdt(LP_TOKEN)),
+                OptionalValue::Some(LP_NONCE),
+            );
+        });
+    world
+        .tx()
+        .from(lp_client)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.register_as_user();
+        });
+
+    for _ in 0..2 {
+        world
+            .tx()
+            .from(lp_client)
+            .to(SC_ADDRESS)
+            .esdt(TestEsdtTransfer(LP_TOKEN, LP_NONCE, 150))
+            .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+                sc.subscribe(2, OptionalValue::None, OptionalValue::None, OptionalValue::None);
+            });
+    }
+    world
+        .tx()
+        .from(lp_client)
+        .to(SC_ADDRESS)
+        .esdt(TestEsdtTransfer(LP_TOKEN, LP_NONCE + 1, 150))
+        .returns(ExpectError(4, "Invalid payment token"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(2, OptionalValue::None, OptionalValue::None, OptionalValue::None);
+        });
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.held_payment(1).get(), PRICE);
+            assert_eq!(sc.held_payment(2).get(), PRICE);
+        });
+
+    world
+        .tx()
+        .from(lp_client)
+        .to(SC_ADDRESS)
+        .esdt(TestEsdtTransfer(LP_TOKEN, LP_NONCE, 20))
+        .esdt(TestEsdtTransfer(LP_TOKEN, LP_NONCE, 30))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.top_up_many(MultiValueEncoded::from_iter([1u64, 2]));
+        });
+    world
+        .tx()
+        .from(lp_client)
+        .to(SC_ADDRESS)
+        .esdt(TestEsdtTransfer(LP_TOKEN, LP_NONCE, 20))
+        .returns(ExpectError(4, "Payments do not match subscriptions"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.top_up_many(MultiValueEncoded::from_iter([1u64, 2]));
+        });
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.subscriptions(1).get().remaining_balance, 70u64);
+            assert_eq!(sc.subscriptions(2).get().remaining_balance, 80u64);
+        });
+    assert_reconciled(&mut world, || EgldOrEsdtTokenIdentifier::esdt(LP_TOKEN), LP_NONCE);
+}
+
+#[test]
+fn failing_hook_does_not_block_payments_test() {
+    let hook = TestSCAddress::new("hook");
+    let mut world = setup();
+    world
+        .tx()
+        .from(OWNER)
+        .raw_deploy()
+        .code(CODE_PATH)
+        .new_address(hook)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.init(OptionalValue::Some(0));
+        });
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Hook must be a smart contract"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_service_hook(1, OptionalValue::Some(CLIENT.to_managed_address()));
+        });
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_service_hook(1, OptionalValue::Some(hook.to_managed_address()));
+        });
+
+    subscribe(&mut world, 250);
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+
+    world.check_account(PROVIDER).balance(2 * PRICE);
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.hook_failures(1).get(), 3);
+        });
+}
+
+fn deactivate_service(world: &mut ScenarioWorld, mode: DeactivationMode) {
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.deactivate_service(1, OptionalValue::Some(mode));
+        });
+}
+
+#[test]
+fn stop_renewals_deactivation_runs_in_batches_test() {
+    let mut world = setup();
+    for _ in 0..51 {
+        subscribe(&mut world, PRICE);
+    }
+    deactivate_service(&mut world, DeactivationMode::StopRenewals);
+    check_status(&mut world, 50, SubscriptionStatus::PendingProviderCancel);
+    check_status(&mut world, 51, SubscriptionStatus::Active);
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Deactivation still in progress"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.reactivate_service(1);
+        });
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.continue_service_deactivation(1);
+            assert!(sc.service_deactivation(1).get().completed);
+        });
+    check_status(&mut world, 51, SubscriptionStatus::PendingProviderCancel);
+
+    world.current_block().block_nonce(11);
+    finalize_cancellation(&mut world, 51);
+    check_status(&mut world, 51, SubscriptionStatus::CancelledByProvider);
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.reactivate_service(1);
+            assert!(sc.services(1).get().active);
+            assert!(sc.service_deactivation(1).is_empty());
+        });
+    subscribe(&mut world, PRICE);
+}
+
+#[test]
+fn terminate_now_refunds_unused_part_of_held_payment_test() {
+    let mut world = setup();
+    subscribe(&mut world, 250);
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.current_block().block_nonce(16);
+
+    deactivate_service(&mut world, DeactivationMode::TerminateNow);
+    check_status(&mut world, 1, SubscriptionStatus::CancelledByProvider);
+    world.check_account(CLIENT).balance(10_000 - 250 + 50 + PRICE / 2);
+    world.check_account(PROVIDER).balance(2 * PRICE - PRICE / 2);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.held_payment(1).get(), 0u64);
+            assert_eq!(sc.service_total_collected(1).get(), 2 * PRICE - PRICE / 2);
+            assert_eq!(sc.subscriptions(1).get().total_spent, 2 * PRICE - PRICE / 2);
+            assert_eq!(sc.get_service_stats(1).cancelled_by_provider, 1);
+        });
+    assert_reconciled(&mut world, EgldOrEsdtTokenIdentifier::egld, 0);
+}
+
+#[test]
+fn terminate_now_returns_dispute_escrow_test() {
+    let mut world = setup_disputed();
+    world.current_block().block_nonce(25);
+
+    deactivate_service(&mut world, DeactivationMode::TerminateNow);
+    check_status(&mut world, 1, SubscriptionStatus::CancelledByProvider);
+    world.check_account(CLIENT).balance(10_000);
+    world.check_account(PROVIDER).balance(0);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert!(sc.disputes(1).is_empty());
+            assert!(sc.service_deactivation(1).get().completed);
+            assert_eq!(sc.service_total_collected(1).get(), 0u64);
+            assert_eq!(sc.subscriptions(1).get().total_spent, 0u64);
+        });
+    assert_reconciled(&mut world, EgldOrEsdtTokenIdentifier::egld, 0);
+}
+
+#[test]
+fn price_change_applies_after_notice_period_test() {
+    let mut world = setup();
+    govern(&mut world, || GovernanceAction::SetMinPriceNotice(20));
+    subscribe(&mut world, 1_000);
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Notice period too short"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.update_service_price(1, BigUint::from(2 * PRICE), 5, 15);
+        });
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.update_service_price(1, BigUint::from(2 * PRICE), 5, 25);
+        });
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let change = sc.get_pending_price_change(1).into_option().unwrap();
+            assert_eq!(change.effective_block, 25);
+            assert_eq!(sc.get_service(1).amount_per_cycle, PRICE);
+        });
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.current_block().block_nonce(21);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(2 * PRICE);
+
+    world.current_block().block_nonce(31);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(3 * PRICE);
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let sub = sc.subscriptions(1).get();
+            assert_eq!(sub.amount_per_cycle, 2 * PRICE);
+            assert_eq!(sub.next_payment_block, 36);
+            assert!(sc.get_pending_price_change(1).is_none());
+            assert_eq!(sc.get_service(1).amount_per_cycle, 2 * PRICE);
+        });
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(PRICE)
+        .returns(ExpectError(4, "Deposit must cover at least one cycle"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(1, OptionalValue::None, OptionalValue::None, OptionalValue::None);
+        });
+}
+
+#[test]
+fn subscription_transfer_moves_balance_to_recipient_test() {
+    let colleague = TestAddress::new("colleague");
+    let mut world = setup();
+    world.account(colleague).nonce(1).balance(0);
+    world
+        .tx()
+        .from(colleague)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.register_as_user();
+        });
+    subscribe(&mut world, 250);
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let mut members = MultiValueEncoded::new();
+            members.push(CLIENT.to_managed_address());
+            sc.add_subscription_members(1, members);
+            sc.propose_subscription_transfer(1, colleague.to_managed_address());
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "No transfer proposed to caller"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.accept_subscription_transfer(1);
+        });
+    world
+        .tx()
+        .from(colleague)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.accept_subscription_transfer(1);
+        });
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.subscriptions(1).get().client, colleague.to_managed_address());
+            assert_eq!(sc.get_user_subscriptions(CLIENT.to_managed_address()).len(), 1);
+            assert!(sc.get_user_active_subscriptions(CLIENT.to_managed_address()).is_empty());
+            assert_eq!(sc.get_user_subscriptions(colleague.to_managed_address()).len(), 1);
+            assert!(sc.is_subscribed(colleague.to_managed_address(), 1));
+            assert!(!sc.is_subscribed(CLIENT.to_managed_address(), 1));
+            assert!(sc.subscription_members(1).is_empty());
+            assert!(sc.get_pending_transfer(1).is_none());
+        });
+
+    world
+        .tx()
+        .from(colleague)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.cancel_subscription_by_user(1);
+        });
+    world.current_block().block_nonce(11);
+    finalize_cancellation(&mut world, 1);
+    world.check_account(colleague).balance(150);
+    world.check_account(CLIENT).balance(10_000 - 250);
+}
+
+#[test]
+fn cancellation_drops_pending_transfer_test() {
+    let colleague = TestAddress::new("colleague");
+    let mut world = setup();
+    world.account(colleague).nonce(1);
+    world
+        .tx()
+        .from(colleague)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.register_as_user();
+        });
+    subscribe(&mut world, 250);
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.propose_subscription_transfer(1, colleague.to_managed_address());
+            sc.cancel_subscription_by_user(1);
+            assert!(sc.get_pending_transfer(1).is_none());
+            sc.revoke_cancellation(1);
+        });
+
+    world
+        .tx()
+        .from(colleague)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "No transfer proposed to caller"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.accept_subscription_transfer(1);
+        });
+}
+
+#[test]
+fn provider_can_disallow_transfers_test() {
+    let mut world = setup();
+    subscribe(&mut world, 250);
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_service_transferable(1, false);
+        });
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Service does not allow transfers"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.propose_subscription_transfer(1, OWNER.to_managed_address());
+        });
+}
+
+#[test]
+fn bundle_subscription_grants_access_to_member_services_test() {
+    let mut world = setup();
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let second = sc.create_service(
+                ManagedBuffer::from("second"),
+                ManagedBuffer::from("description"),
+                BigUint::from(PRICE),
+                FREQUENCY,
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+            let bundle = sc.create_bundle(
+                ManagedBuffer::from("bundle"),
+                ManagedBuffer::from("both services"),
+                BigUint::from(160u64),
+                FREQUENCY,
+                MultiValueEncoded::from_iter([1u64, second]),
+            );
+            assert_eq!(bundle, 3);
+        });
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Bundles cannot be nested"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.create_bundle(
+                ManagedBuffer::from("nested"),
+                ManagedBuffer::from("description"),
+                BigUint::from(PRICE),
+                FREQUENCY,
+                MultiValueEncoded::from_iter([1u64, 3]),
+            );
+        });
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(160)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(3, OptionalValue::None, OptionalValue::None, OptionalValue::None);
+        });
+    world.check_account(PROVIDER).balance(0);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let client = CLIENT.to_managed_address();
+            assert!(sc.is_subscribed(client.clone(), 1));
+            assert!(sc.is_subscribed(client.clone(), 2));
+            assert!(sc.is_subscribed(client.clone(), 3));
+            assert_eq!(sc.has_access_until(client, 2), 1 + FREQUENCY);
+            assert!(sc.service_bundles(2).contains(&3));
+            assert_eq!(sc.held_payment(1).get(), 160u64);
+        });
+
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_service_access_mode(2, AccessMode::Blocklist);
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(CLIENT.to_managed_address());
+            sc.add_to_service_blocklist(2, false, addresses);
+            sc.deactivate_service(1, OptionalValue::None);
+        });
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let client = CLIENT.to_managed_address();
+            assert!(!sc.is_subscribed(client.clone(), 1));
+            assert!(!sc.is_subscribed(client.clone(), 2));
+            assert!(sc.is_subscribed(client.clone(), 3));
+            assert!(!sc.can_subscribe(3, client));
+        });
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(160)
+        .returns(ExpectError(4, "Not allowed to use a bundled service"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(3, OptionalValue::None, OptionalValue::None, OptionalValue::None);
+        });
+}
+
+#[test]
+fn team_seats_are_prorated_and_members_get_access_test() {
+    let member = TestAddress::new("member");
+    let mut world = setup();
+    world.account(member).nonce(1).balance(0);
+    subscribe(&mut world, 1_000);
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "More members than seats"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let mut members = MultiValueEncoded::new();
+            members.push(member.to_managed_address());
+            members.push(OWNER.to_managed_address());
+            sc.add_subscription_members(1, members);
+        });
+
+    world.current_block().block_nonce(6);
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.set_seats(1, 3);
+            let mut members = MultiValueEncoded::new();
+            members.push(member.to_managed_address());
+            sc.add_subscription_members(1, members);
+        });
+    world.check_account(PROVIDER).balance(0);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let sub = sc.subscriptions(1).get();
+            assert_eq!(sub.seats, 3);
+            assert_eq!(sub.remaining_balance, BigUint::from(800u64));
+            assert_eq!(sc.held_payment(1).get(), 2 * PRICE);
+            assert!(sc.is_subscribed(member.to_managed_address(), 1));
+            assert_eq!(sc.has_access_until(member.to_managed_address(), 1), 11);
+        });
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    world.check_account(PROVIDER).balance(2 * PRICE);
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let mut members = MultiValueEncoded::new();
+            members.push(member.to_managed_address());
+            sc.remove_subscription_members(1, members);
+        });
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert!(!sc.is_subscribed(member.to_managed_address(), 1));
+        });
+    assert_reconciled(&mut world, EgldOrEsdtTokenIdentifier::egld, 0);
+}
+
+#[test]
+fn governance_actions_wait_for_timelock_test() {
+    let mut world = setup();
+    govern(&mut world, || GovernanceAction::SetGovernanceDelay(GOVERNANCE_DELAY));
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Invalid share"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.propose_governance_action(GovernanceAction::SetDisputeDefaultProviderShare(10_001));
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.propose_governance_action(GovernanceAction::SetMinPriceNotice(0)), 2);
+            assert_eq!(sc.propose_governance_action(GovernanceAction::SetDisputeTimeout(7)), 3);
+        });
+
+    world.current_block().block_nonce(GOVERNANCE_DELAY);
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Timelock not expired"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.execute_governance_action(2);
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.cancel_governance_action(3);
+            assert_eq!(sc.get_pending_governance_proposals().len(), 1);
+        });
+
+    world.current_block().block_nonce(1 + GOVERNANCE_DELAY);
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.execute_governance_action(2);
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Proposal not pending"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.execute_governance_action(3);
+        });
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.min_price_notice().get(), 0);
+            assert_eq!(sc.dispute_timeout().get(), 100_800);
+            assert!(sc.get_pending_governance_proposals().is_empty());
+            assert!(sc.get_governance_proposal(2).is_none());
+        });
+}
+
+fn create_bonded_service(world: &mut ScenarioWorld, provider: TestAddress, bond: u64) -> u64 {
+    let mut service_id = 0;
+    world
+        .tx()
+        .from(provider)
+        .to(SC_ADDRESS)
+        .egld(bond)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            service_id = sc.create_service(
+                ManagedBuffer::from("bonded"),
+                ManagedBuffer::from("description"),
+                BigUint::from(PRICE),
+                FREQUENCY,
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+        });
+    service_id
+}
+
+#[test]
+fn service_bond_is_refunded_on_retirement_and_slashable_test() {
+    let bonded_provider = TestAddress::new("bonded-provider");
+    let mut world = setup();
+    world.account(bonded_provider).nonce(1).balance(1_000);
+    world.account(ARBITER).nonce(1);
+    govern(&mut world, || GovernanceAction::SetServiceBond(BigUint::from(50u64)));
+    govern(&mut world, || GovernanceAction::SetModerator(ARBITER.to_managed_address(), true));
+    world
+        .tx()
+        .from(bonded_provider)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.register_as_provider();
+        });
+    world
+        .tx()
+        .from(bonded_provider)
+        .to(SC_ADDRESS)
+        .egld(10)
+        .returns(ExpectError(4, "Invalid service bond"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.create_service(
+                ManagedBuffer::from("bonded"),
+                ManagedBuffer::from("description"),
+                BigUint::from(PRICE),
+                FREQUENCY,
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+        });
+
+    let retired_id = create_bonded_service(&mut world, bonded_provider, 50);
+    let abusive_id = create_bonded_service(&mut world, bonded_provider, 50);
+    let kept_id = create_bonded_service(&mut world, bonded_provider, 50);
+    world
+        .tx()
+        .from(bonded_provider)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Service is still active"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.retire_service(retired_id);
+        });
+    world
+        .tx()
+        .from(bonded_provider)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.deactivate_service(retired_id, OptionalValue::None);
+        });
+    world
+        .tx()
+        .from(bonded_provider)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Retirement cooldown not over"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.retire_service(retired_id);
+        });
+
+    world.current_block().block_nonce(1 + 100);
+    govern(&mut world, || GovernanceAction::SetBondCooldown(100));
+    world
+        .tx()
+        .from(bonded_provider)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.retire_service(retired_id);
+        });
+    world.check_account(bonded_provider).balance(900);
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let ids: Vec<u64> = sc.get_all_service_ids().into_iter().collect();
+            assert_eq!(ids, vec![1, kept_id, abusive_id]);
+        });
+    world
+        .tx()
+        .from(bonded_provider)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Service is delisted"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.reactivate_service(retired_id);
+        });
+
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(250)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.subscribe(
+                abusive_id,
+                OptionalValue::None,
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+        });
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Only owner or moderator"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.delist_service(abusive_id);
+        });
+    world
+        .tx()
+        .from(ARBITER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.slash_service_bond(abusive_id);
+            sc.delist_service(abusive_id);
+        });
+    world.check_account(OWNER).balance(50);
+    world.check_account(bonded_provider).balance(900);
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert!(!sc.services(abusive_id).get().active);
+            assert!(sc.service_delisted(abusive_id).get());
+            let ids: Vec<u64> = sc.get_all_service_ids().into_iter().collect();
+            assert_eq!(ids, vec![1, kept_id]);
+            assert_eq!(sc.subscriptions(1).get().status, SubscriptionStatus::Active);
+        });
+    world
+        .tx()
+        .from(bonded_provider)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Service has active subscriptions"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.retire_service(abusive_id);
+        });
+    assert_reconciled(&mut world, EgldOrEsdtTokenIdentifier::egld, 0);
+}
+
+/// Deploys the schema version 1 contract from `legacy/scheduled-payments-v1`
+/// and runs it: one service, an active subscription and a cancelled one.
+fn setup_legacy() -> ScenarioWorld {
+    let mut world = world();
+    world.register_contract(LEGACY_CODE_PATH, scheduled_payments_contract_v1::ContractBuilder);
+    world.account(OWNER).nonce(1);
+    world.account(PROVIDER).nonce(1).balance(0);
+    world.account(CLIENT).nonce(1).balance(10_000);
+    world.current_block().block_nonce(1);
+
+    world
+        .tx()
+        .from(OWNER)
+        .raw_deploy()
+        .code(LEGACY_CODE_PATH)
+        .new_address(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract_v1::contract_obj, |sc| {
+            sc.init();
+        });
+    world
+        .tx()
+        .from(PROVIDER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract_v1::contract_obj, |sc| {
+            sc.register_as_provider();
+            sc.create_service(
+                ManagedBuffer::from("service"),
+                ManagedBuffer::from("description"),
+                BigUint::from(PRICE),
+                FREQUENCY,
+                OptionalValue::None,
+            );
+        });
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract_v1::contract_obj, |sc| {
+            sc.register_as_user();
+        });
+    for deposit in [250u64, PRICE] {
+        world
+            .tx()
+            .from(CLIENT)
+            .to(SC_ADDRESS)
+            .egld(deposit)
+            .whitebox(scheduled_payments_contract_v1::contract_obj, |sc| {
+                sc.subscribe(1);
+            });
+    }
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract_v1::contract_obj, |sc| {
+            sc.cancel_subscription_by_user(2);
+        });
+
+    world.current_block().block_nonce(11);
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract_v1::contract_obj, |sc| {
+            sc.finalize_cancellation(2);
+        });
+    world.check_account(PROVIDER).balance(2 * PRICE);
+    world
+}
+
+#[test]
+fn upgrade_migrates_legacy_records_in_batches_test() {
+    let mut world = setup_legacy();
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract_v1::contract_obj, |sc| {
+            sc.trigger_payment(1);
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .typed(scheduled_payments_proxy::SubscriptionContractProxy)
+        .upgrade()
+        .code(CODE_PATH)
+        .run();
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.schema_version().get(), 1);
+            assert_eq!(sc.dispute_timeout().get(), 100_800);
+            assert!(!sc.migrate_records(2));
+        });
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let migration = sc.get_schema_migration().into_option().unwrap();
+            assert_eq!(migration.next_service_id, 2);
+            assert_eq!(migration.next_subscription_id, 2);
+            assert_eq!(sc.services(1).get().token_nonce, 0);
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Migration in progress"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.trigger_payment(1);
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "Migration in progress"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            sc.propose_governance_action(GovernanceAction::SweepSurplus(
+                EgldOrEsdtTokenIdentifier::egld(),
+                0,
+            ));
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert!(sc.migrate_records(2));
+        });
+
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.schema_version().get(), 2);
+            assert!(sc.get_schema_migration().is_none());
+            let sub = sc.subscriptions(1).get();
+            assert_eq!(sub.status, SubscriptionStatus::Active);
+            assert_eq!(sub.remaining_balance, BigUint::from(50u64));
+            assert_eq!(sub.total_spent, PRICE);
+            assert_eq!(sub.cycles_paid, 1);
+            assert_eq!(sub.seats, 1);
+            assert_eq!(
+                sc.subscriptions(2).get().status,
+                SubscriptionStatus::CancelledByUser
+            );
+            let stats = sc.service_stats(1).get();
+            assert_eq!(stats.lifetime_subscriptions, 2);
+            assert_eq!(stats.active_subscribers, 1);
+            assert_eq!(stats.cancelled_by_user, 1);
+            assert_eq!(sc.service_committed_escrow(1).get(), BigUint::from(50u64));
+            assert_eq!(sc.service_total_collected(1).get(), 2 * PRICE);
+            assert_eq!(sc.has_access_until(CLIENT.to_managed_address(), 1), 21);
+            assert_eq!(sc.get_services_page(0, 10, true, OptionalValue::None).len(), 1);
+            assert_eq!(sc.service_listing_index(1).get(), 1);
+        });
+    assert_reconciled(&mut world, EgldOrEsdtTokenIdentifier::egld, 0);
+
+    // The cycle in progress was paid out by version 1, so terminating
+    // mid-cycle only returns the balance.
+    world.current_block().block_nonce(16);
+    deactivate_service(&mut world, DeactivationMode::TerminateNow);
+    check_status(&mut world, 1, SubscriptionStatus::CancelledByProvider);
+    world.check_account(CLIENT).balance(10_000 - 250 - PRICE + 50);
+    world.check_account(PROVIDER).balance(3 * PRICE);
+    world
+        .query()
+        .to(SC_ADDRESS)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            assert_eq!(sc.subscriptions(1).get().total_spent, PRICE);
+            assert_eq!(sc.service_total_collected(1).get(), 2 * PRICE);
+        });
+    assert_reconciled(&mut world, EgldOrEsdtTokenIdentifier::egld, 0);
+}
+
+#[test]
+fn owner_sweeps_only_the_surplus_test() {
+    let mut world = setup();
+    subscribe(&mut world, 250);
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .returns(ExpectError(4, "No surplus"))
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let proposal_id = sc.propose_governance_action(GovernanceAction::SweepSurplus(
+                EgldOrEsdtTokenIdentifier::egld(),
+                0,
+            ));
+            sc.execute_governance_action(proposal_id);
+        });
+
+    // A stray transfer that no subscription accounts for.
+    world
+        .tx()
+        .from(CLIENT)
+        .to(SC_ADDRESS)
+        .egld(30)
+        .whitebox(scheduled_payments_contract::contract_obj, |sc| {
+            let reconciliation = sc.get_reconciliation(EgldOrEsdtTokenIdentifier::egld(), 0);
+            assert_eq!(reconciliation.liabilities, BigUint::from(250u64));
+            assert_eq!(reconciliation.surplus, BigUint::from(30u64));
+        });
+    govern(&mut world, || GovernanceAction::SweepSurplus(EgldOrEsdtTokenIdentifier::egld(), 0));
+    world.check_account(OWNER).balance(30);
+    assert_reconciled(&mut world, EgldOrEsdtTokenIdentifier::egld, 0);
+
+    world.current_block().block_nonce(11);
+    trigger_payment(&mut world, 1);
+    assert_reconciled(&mut world, EgldOrEsdtTokenIdentifier::egld, 0);
+}
@@ -5,9 +5,11 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           22
+// Upgrade:                              1
+// Endpoints:                          134
 // Async Callback (empty):               1
-// Total number of exported functions:  24
+// Promise callbacks:                    1
+// Total number of exported functions: 138
 
 #![no_std]
 
@@ -18,28 +20,142 @@ multiversx_sc_wasm_adapter::endpoints! {
     scheduled_payments_contract
     (
         init => init
+        upgrade => upgrade
+        migrateRecords => migrate_records
+        proposeGovernanceAction => propose_governance_action
+        executeGovernanceAction => execute_governance_action
+        cancelGovernanceAction => cancel_governance_action
         registerAsUser => register_as_user
         registerAsProvider => register_as_provider
         createService => create_service
+        createBundle => create_bundle
         deactivateService => deactivate_service
+        continueServiceDeactivation => continue_service_deactivation
+        reactivateService => reactivate_service
+        retireService => retire_service
+        updateServicePrice => update_service_price
+        setServiceMetadata => set_service_metadata
+        setServiceMetering => set_service_metering
+        setServiceCommitment => set_service_commitment
+        setServiceHook => set_service_hook
+        setServiceTransferable => set_service_transferable
+        setServiceAccessMode => set_service_access_mode
+        addToServiceAllowlist => add_to_service_allowlist
+        removeFromServiceAllowlist => remove_from_service_allowlist
+        addToServiceBlocklist => add_to_service_blocklist
+        removeFromServiceBlocklist => remove_from_service_blocklist
+        delistService => delist_service
+        slashServiceBond => slash_service_bond
         subscribe => subscribe
         topUp => top_up
+        topUpMany => top_up_many
+        setRevivalApproval => set_revival_approval
+        reviveSubscription => revive_subscription
         cancelSubscriptionByUser => cancel_subscription_by_user
         cancelSubscriptionByProvider => cancel_subscription_by_provider
+        revokeCancellation => revoke_cancellation
+        proposeSubscriptionTransfer => propose_subscription_transfer
+        cancelSubscriptionTransfer => cancel_subscription_transfer
+        acceptSubscriptionTransfer => accept_subscription_transfer
+        setSeats => set_seats
+        addSubscriptionMembers => add_subscription_members
+        removeSubscriptionMembers => remove_subscription_members
+        reportUsage => report_usage
+        disputeUsage => dispute_usage
+        openDispute => open_dispute
+        resolveDispute => resolve_dispute
+        expireDispute => expire_dispute
         triggerPayment => trigger_payment
         finalizeCancellation => finalize_cancellation
-        getService => services
-        getSubscription => subscriptions
+        archiveSubscription => archive_subscription
+        getService => get_service
+        getSchemaVersion => schema_version
         getAllServiceIds => get_all_service_ids
         getProviderServices => get_provider_services
         getUserSubscriptions => get_user_subscriptions
         getProviderSubscriptions => get_provider_subscriptions
         getServiceSubscriptions => get_service_subscriptions
+        getServicesPage => get_services_page
+        getProviderServicesPage => get_provider_services_page
+        getUserSubscriptionsPage => get_user_subscriptions_page
+        getProviderSubscriptionsPage => get_provider_subscriptions_page
+        getServiceSubscriptionsPage => get_service_subscriptions_page
+        getUserActiveSubscriptions => get_user_active_subscriptions
+        getProviderActiveSubscriptions => get_provider_active_subscriptions
+        getServiceActiveSubscriptions => get_service_active_subscriptions
+        isSubscribed => is_subscribed
+        hasAccessUntil => has_access_until
+        getSubscriptionSummary => get_subscription_summary
+        getUserSubscriptionHistory => get_user_subscription_history
+        getActiveServicesByCategory => get_active_services_by_category
+        getActiveServicesByTag => get_active_services_by_tag
+        getActiveServicesByToken => get_active_services_by_token
+        getCategoryServiceCount => get_category_service_count
+        getTagServiceCount => get_tag_service_count
+        getTokenServiceCount => get_token_service_count
+        getServiceCount => get_service_count
+        getProviderServiceCount => get_provider_service_count
+        getUserSubscriptionCount => get_user_subscription_count
+        getProviderSubscriptionCount => get_provider_subscription_count
+        getServiceSubscriptionCount => get_service_subscription_count
+        getSubscription => get_subscription
         getSubscriptionPaymentInfo => get_subscription_payment_info
         getSubscriptionState => get_subscription_state
+        getPendingUsage => get_pending_usage
+        getServiceDeactivation => get_service_deactivation
+        getPendingPriceChange => get_pending_price_change
+        isServiceTransferable => is_service_transferable
+        getReconciliation => get_reconciliation
+        getSchemaMigration => get_schema_migration
+        getGovernanceProposal => get_governance_proposal
+        getPendingGovernanceProposals => get_pending_governance_proposals
+        getPendingTransfer => get_pending_transfer
+        getDispute => get_dispute
+        getPaymentHistory => get_payment_history
+        getInvoice => get_invoice
+        getServiceStats => get_service_stats
+        getProviderStats => get_provider_stats
+        canSubscribe => can_subscribe
+        getServiceAllowlist => get_service_allowlist
+        getServiceBlocklist => get_service_blocklist
         getUserRole => get_user_role
         getLastServiceId => last_service_id
         getLastSubscriptionId => last_subscription_id
+        getUsageDisputeWindow => usage_dispute_window
+        getServiceMetering => service_metering
+        getSubscriptionMetering => subscription_metering
+        getServiceCommitment => service_commitment
+        getServiceMetadata => service_metadata
+        getTotalLiabilities => total_liabilities
+        getServiceBondAmount => service_bond_amount
+        getBondCooldown => bond_cooldown
+        getServiceBond => service_bond
+        getServiceDeactivatedBlock => service_deactivated_block
+        isServiceDelisted => service_delisted
+        getModerators => moderators
+        getGovernanceDelay => governance_delay
+        getLastGovernanceProposalId => last_governance_proposal_id
+        getMinPriceNotice => min_price_notice
+        getSubscriptionMembers => subscription_members
+        getMemberSubscriptions => member_subscriptions
+        getBundleServices => bundle_services
+        getServiceBundles => service_bundles
+        getServiceHook => service_hook
+        getHookFailures => hook_failures
+        getServiceAccessMode => service_access_mode
+        getArbiter => arbiter
+        getServiceArbiter => service_arbiter
+        getDisputeTimeout => dispute_timeout
+        getDisputeDefaultProviderShare => dispute_default_provider_share
+        getHeldPayment => held_payment
+        isRevivalApproved => revival_approved
+        getSettlementCount => settlement_count
+        getLastInvoiceNumber => last_invoice_number
+        getServiceTotalCollected => service_total_collected
+        getProviderTotalCollected => provider_total_collected
+        getServiceCommittedEscrow => service_committed_escrow
+        getProviderCommittedEscrow => provider_committed_escrow
+        hook_callback => hook_callback
     )
 }
 